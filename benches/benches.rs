@@ -12,5 +12,50 @@ pub fn sieve_of_atkin(c: &mut criterion::Criterion) {
     });
 }
 
-criterion::criterion_group!(benches, is_prime, sieve_of_atkin);
+pub fn sieve_of_atkin_parallel(c: &mut criterion::Criterion) {
+    let mut group = c.benchmark_group("sieve_of_atkin_parallel");
+    for limit in [10usize.pow(6), 10usize.pow(8)] {
+        group.bench_with_input(criterion::BenchmarkId::new("serial", limit), &limit, |b, &limit| {
+            b.iter(|| utils::SieveOfAtkin::new(limit))
+        });
+        group.bench_with_input(criterion::BenchmarkId::new("parallel", limit), &limit, |b, &limit| {
+            b.iter(|| utils::SieveOfAtkin::new_parallel(limit, None))
+        });
+    }
+    group.finish();
+}
+
+pub fn long_multiplication(c: &mut criterion::Criterion) {
+    let mut group = c.benchmark_group("long_multiplication");
+    for exp in [100, 1000, 10000] {
+        // Operands have roughly 18 times as many decimal digits as the
+        // exponent.
+        let multiplicand = utils::Long::new("987654321987654321").pow(exp);
+        let multiplier = utils::Long::new("123456789123456789").pow(exp);
+        let operands = (&multiplicand, &multiplier);
+        group.bench_with_input(
+            criterion::BenchmarkId::new("schoolbook", exp),
+            &operands,
+            |b, (x, y)| b.iter(|| x.mul_schoolbook(y)),
+        );
+        group.bench_with_input(criterion::BenchmarkId::new("karatsuba", exp), &operands, |b, (x, y)| {
+            b.iter(|| x.mul_karatsuba(y))
+        });
+        group.bench_with_input(criterion::BenchmarkId::new("ntt", exp), &operands, |b, (x, y)| {
+            b.iter(|| x.mul_ntt(y))
+        });
+        group.bench_with_input(criterion::BenchmarkId::new("square", exp), &multiplicand, |b, x| {
+            b.iter(|| x.square())
+        });
+    }
+    group.finish();
+}
+
+criterion::criterion_group!(
+    benches,
+    is_prime,
+    sieve_of_atkin,
+    sieve_of_atkin_parallel,
+    long_multiplication
+);
 criterion::criterion_main!(benches);
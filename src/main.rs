@@ -79,11 +79,13 @@ fn solve_and_time_one(problem_number: usize) -> bool {
         69 => totient_maximum::solve,
         71 => ordered_fractions::solve,
         72 => counting_fractions::solve,
+        73 => counting_fractions_in_a_range::solve,
         74 => digit_factorial_chains::solve,
         75 => singular_integer_right_triangles::solve,
         76 => counting_summations::solve,
         77 => prime_summations::solve,
         78 => coin_partitions::solve,
+        80 => square_root_digital_expansion::solve,
         81 => path_sum_two_ways::solve,
         85 => counting_rectangles::solve,
         87 => prime_power_triples::solve,
@@ -8,6 +8,7 @@ pub mod combinatoric_selections;
 pub mod consecutive_prime_sum;
 pub mod convergents_of_e;
 pub mod counting_fractions;
+pub mod counting_fractions_in_a_range;
 pub mod counting_rectangles;
 pub mod counting_summations;
 pub mod counting_sundays;
@@ -70,6 +71,7 @@ pub mod special_pythagorean_triplet;
 pub mod spiral_primes;
 pub mod square_digit_chains;
 pub mod square_root_convergents;
+pub mod square_root_digital_expansion;
 pub mod sub_string_divisibility;
 pub mod sum_square_difference;
 pub mod summation_of_primes;
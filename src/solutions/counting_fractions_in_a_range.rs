@@ -0,0 +1,8 @@
+use crate::utils;
+
+pub fn solve() -> i64 {
+    let result = utils::Farey::count(12000, utils::Rational::new(1, 3), utils::Rational::new(1, 2));
+
+    assert_eq!(result, 7295372);
+    result
+}
@@ -1,7 +1,7 @@
 use crate::utils;
 
 pub fn solve() -> i64 {
-    let mut result = (1, 1);
+    let mut result = utils::Rational::from(1);
     for denominator in 11..=99 {
         for numerator in 10..denominator {
             let num = utils::Digits::new(numerator).collect::<Vec<i64>>();
@@ -16,12 +16,11 @@ pub fn solve() -> i64 {
                 // value.
                 && num[0] * num[1] * denominator == den[0] * den[1] * numerator
             {
-                result = (result.0 * numerator, result.1 * denominator);
+                result *= utils::Rational::new(numerator, denominator);
             }
         }
     }
-    let gcd = utils::gcd(result.0, result.1);
-    let denominator = result.1 / gcd;
+    let denominator = result.denominator();
 
     assert_eq!(denominator, 100);
     denominator
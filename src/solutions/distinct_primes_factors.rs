@@ -4,18 +4,21 @@ use crate::utils;
 ///
 /// Returns the first of the four integers.
 fn four_distinct() -> i64 {
-    let primes = utils::SieveOfAtkin::new(1000).iter().collect::<Vec<i64>>();
+    // The result is not known in advance, so sieve up to some limit and
+    // double it whenever the search reaches it.
+    let mut omega = utils::LinearSieve::new(1 << 16).omega();
     let mut num = 644;
     let mut obtained = 0;
     let mut required = 4;
     loop {
+        if num as usize + 4 > omega.len() {
+            omega = utils::LinearSieve::new(2 * (omega.len() - 1)).omega();
+        }
         // Search backwards (similar to how it's done in the Boyer-Moore
         // substring searching algorithm) so that we can skip forwards,
         // avoiding unnecessary computations.
         for n in (num..num + 4).rev() {
-            // Try to terminate the iterator as early as possible by taking
-            // only as many prime divisors as required.
-            if primes.iter().filter(|&&prime| n % prime == 0).take(4).count() == 4 {
+            if omega[n as usize] >= 4 {
                 required -= 1;
                 if required == 0 {
                     return num;
@@ -104,10 +104,7 @@ pub fn solve() -> i64 {
         "53503534226472524250874054075591789781264330331690",
     ];
 
-    let mut sum = utils::Long::from(0);
-    for num in &nums {
-        sum += &utils::Long::new(num);
-    }
+    let sum = nums.iter().map(|num| utils::Long::new(num)).sum::<utils::Long>();
     let result = sum.to_string();
     let result: i64 = result[0..10].parse().unwrap();
 
@@ -1,12 +1,12 @@
 use crate::utils;
 
 pub fn solve() -> i64 {
-    let result = (0..10000)
+    let result = (0..10000u32)
         .filter(|&num| {
             let mut num = utils::Long::from(num);
-            num += &num.reverse();
+            num += &num.reversed();
             for _ in 1..50 {
-                let num_rev = num.reverse();
+                let num_rev = num.reversed();
                 if num == num_rev {
                     return false;
                 }
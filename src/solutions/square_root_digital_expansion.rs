@@ -0,0 +1,15 @@
+use crate::utils;
+
+pub fn solve() -> i64 {
+    // Scaling a number by an even power of 10 before taking its square root
+    // shifts the decimal point of the result. Each square root below has one
+    // digit before the decimal point, so keep 99 digits after it.
+    let scale = utils::Long::from(10u32).pow(198);
+    let result = (1..=100u32)
+        .filter(|&num| utils::isqrt(num as i64).pow(2) != num as i64)
+        .map(|num| (&scale * num).isqrt().sum())
+        .sum();
+
+    assert_eq!(result, 40886);
+    result
+}
@@ -1,8 +1,8 @@
 use crate::utils;
 
 pub fn solve() -> i64 {
-    const LIMIT: usize = 2000000;
-    let result: i64 = utils::SieveOfAtkin::new(LIMIT).iter().sum();
+    const LIMIT: i64 = 2000000;
+    let result = utils::prime_sum(LIMIT - 1) as i64;
 
     assert_eq!(result, 142913828922);
     result
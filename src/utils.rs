@@ -15,15 +15,26 @@ pub fn is_prime(num: i64) -> bool {
     // Slow checks.
     match num {
         ..=100000 => is_prime_td(num),
+        _ => is_prime_u64(num as u64),
+    }
+}
+
+/// Check whether the given number is prime using the Miller-Rabin test. This
+/// handles all numbers which fit in 64 bits, including those which do not fit
+/// in a signed integer.
+///
+/// * `num` Must not be divisible by 2, 3 or 5. Must exceed 5.
+fn is_prime_u64(num: u64) -> bool {
+    match num {
         // The Miller-Rabin tests as performed below are deterministic for all
         // possible inputs. I chose the thresholds (after consulting some
         // tables) such that each is two digits longer than the previous.
-        ..=38010306 => is_prime_mr(num, &vec![2, 9332593]),
-        ..=1050535500 => is_prime_mr(num, &vec![336781006125, 9639812373923155]),
-        ..=273919523040 => is_prime_mr(num, &vec![15, 7363882082, 992620450144556]),
-        ..=31858317218646 => is_prime_mr(num, &vec![2, 642735, 553174392, 3046413974]),
-        ..=3770579582154546 => is_prime_mr(num, &vec![2, 2570940, 880937, 610386380, 4130785767]),
-        _ => is_prime_mr(num, &vec![2, 325, 9375, 28178, 450775, 9780504, 1795265022]),
+        ..=38010306 => is_prime_mr(num, &[2, 9332593]),
+        ..=1050535500 => is_prime_mr(num, &[336781006125, 9639812373923155]),
+        ..=273919523040 => is_prime_mr(num, &[15, 7363882082, 992620450144556]),
+        ..=31858317218646 => is_prime_mr(num, &[2, 642735, 553174392, 3046413974]),
+        ..=3770579582154546 => is_prime_mr(num, &[2, 2570940, 880937, 610386380, 4130785767]),
+        _ => is_prime_mr(num, &[2, 325, 9375, 28178, 450775, 9780504, 1795265022]),
     }
 }
 
@@ -41,12 +52,12 @@ fn is_prime_td(num: i64) -> bool {
 ///
 /// * `num` Must not be divisible by 2, 3 or 5. Must exceed 5.
 /// * `bases` Bases to perform the test with.
-fn is_prime_mr(num: i64, bases: &Vec<i64>) -> bool {
+fn is_prime_mr(num: u64, bases: &[u64]) -> bool {
     let num_minus_1 = num - 1;
     let twopower = num_minus_1.trailing_zeros();
     let multiplier = num_minus_1 >> twopower;
     'bases: for &base in bases {
-        let mut residue = pow(base, multiplier as u64, num);
+        let mut residue = pow_mod(base % num, multiplier, num);
         // If this is 0, it means a wrong base was chosen, so the test is
         // inconclusive. Hence, I group it together with the two cases in which
         // it is suspected to be prime. This ensures that I never mislabel a
@@ -55,7 +66,7 @@ fn is_prime_mr(num: i64, bases: &Vec<i64>) -> bool {
             continue;
         }
         for _ in 1..twopower {
-            residue = pow(residue, 2, num);
+            residue = mul_mod(residue, residue, num);
             if residue == 1 {
                 return false;
             }
@@ -68,6 +79,115 @@ fn is_prime_mr(num: i64, bases: &Vec<i64>) -> bool {
     true
 }
 
+/// Factorise the given number. See `factorise_u64`.
+///
+/// * `num` Must be positive.
+///
+/// Returns the prime factors (in ascending order) and their exponents.
+pub fn factorise(num: i64) -> Vec<(i64, u32)> {
+    if num <= 0 {
+        panic!("only positive numbers can be factorised");
+    }
+    factorise_u64(num as u64)
+        .into_iter()
+        .map(|(prime, exponent)| (prime as i64, exponent))
+        .collect()
+}
+
+/// Factorise the given number. Factors below 1000 are found using trial
+/// division; larger ones are found using Brent's variant of Pollard's rho
+/// algorithm, and checked for primality using the Miller-Rabin test. All
+/// numbers which fit in 64 bits are supported.
+///
+/// * `num` Must be positive.
+///
+/// Returns the prime factors (in ascending order) and their exponents.
+pub fn factorise_u64(mut num: u64) -> Vec<(u64, u32)> {
+    if num == 0 {
+        panic!("only positive numbers can be factorised");
+    }
+    let mut factors = vec![];
+    for prime in [2, 3, 5].into_iter().chain(PotentialPrimes::new(1000)) {
+        let prime = prime as u64;
+        if prime * prime > num {
+            break;
+        }
+        while num.is_multiple_of(prime) {
+            num /= prime;
+            factors.push(prime);
+        }
+    }
+
+    // Whatever remains has no factors less than 1000. Split it until only
+    // prime numbers are left.
+    let mut composites = vec![num];
+    while let Some(num) = composites.pop() {
+        if num == 1 {
+            continue;
+        }
+        // Numbers less than 1000000 without small factors are prime.
+        if num < 1000000 || is_prime_u64(num) {
+            factors.push(num);
+            continue;
+        }
+        let factor = pollard_brent(num);
+        composites.push(factor);
+        composites.push(num / factor);
+    }
+    factors.sort();
+    factors
+        .chunk_by(|a, b| a == b)
+        .map(|chunk| (chunk[0], chunk.len() as u32))
+        .collect()
+}
+
+/// Find a non-trivial factor of the given number using Brent's variant of
+/// Pollard's rho algorithm. The differences between terms of a pseudorandom
+/// sequence are multiplied together in batches, so that a greatest common
+/// divisor is calculated only once per batch.
+///
+/// * `num` Must be composite. Must not be divisible by 2.
+fn pollard_brent(num: u64) -> u64 {
+    const BATCH_LEN: u64 = 128;
+    // If the sequence cycles before a factor is found, try a different one.
+    for c in 1..num {
+        let next = |x: u64| ((mul_mod(x, x, num) as u128 + c as u128) % num as u128) as u64;
+        let (mut x, mut y, mut ys, mut product, mut factor) = (2, 2, 2, 1, 1);
+        let mut len = 1;
+        while factor == 1 {
+            x = y;
+            for _ in 0..len {
+                y = next(y);
+            }
+            let mut steps = 0;
+            while steps < len && factor == 1 {
+                ys = y;
+                for _ in 0..BATCH_LEN.min(len - steps) {
+                    y = next(y);
+                    product = mul_mod(product, x.abs_diff(y), num);
+                }
+                factor = gcd_u64(product, num);
+                steps += BATCH_LEN;
+            }
+            len *= 2;
+        }
+        if factor == num {
+            // The batch overshot. Repeat it one step at a time.
+            loop {
+                ys = next(ys);
+                factor = gcd_u64(x.abs_diff(ys), num);
+                if factor > 1 {
+                    break;
+                }
+            }
+        }
+        if factor != num {
+            return factor;
+        }
+    }
+    unreachable!();
+}
+
 /// Check whether the given number is a palindrome.
 ///
 /// * `num`
@@ -87,6 +207,14 @@ pub fn is_palindrome(num: i64, radix: i32) -> bool {
 /// * `a` Must be non-negative.
 /// * `b` Must be non-negative.
 pub fn gcd(a: i64, b: i64) -> i64 {
+    gcd_u64(a as u64, b as u64) as i64
+}
+
+/// Calculate the greatest common divisor of two numbers.
+///
+/// * `a`
+/// * `b`
+fn gcd_u64(a: u64, b: u64) -> u64 {
     if a == 0 {
         return b;
     }
@@ -164,21 +292,40 @@ pub fn prev_permutation<T: Copy + std::cmp::Ord>(slice: &mut [T]) -> bool {
 ///
 /// * `base` Number to be exponentiated.
 /// * `exp` Exponent.
-/// * `modulus` Modulus.
+/// * `modulus` Modulus. Must be positive.
 pub fn pow(base: i64, exp: u64, modulus: i64) -> i64 {
-    let (mut base, mut exp, modulus, mut multiplier) = (base as i128, exp, modulus as i128, 1);
+    pow_mod(base.rem_euclid(modulus) as u64, exp, modulus as u64) as i64
+}
+
+/// Perform modular exponentiation.
+///
+/// * `base` Number to be exponentiated. Must be less than the modulus.
+/// * `exp` Exponent.
+/// * `modulus` Modulus. Must be positive.
+fn pow_mod(mut base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let mut multiplier = 1;
     loop {
         if exp % 2 == 1 {
-            multiplier = multiplier * base % modulus;
+            multiplier = mul_mod(multiplier, base, modulus);
         }
         if exp <= 1 {
-            return multiplier as i64;
+            return multiplier;
         }
         exp /= 2;
-        base = base.pow(2) % modulus;
+        base = mul_mod(base, base, modulus);
     }
 }
 
+/// Perform modular multiplication. The product is calculated using 128-bit
+/// integers, so it cannot overflow.
+///
+/// * `a` Must be less than the modulus.
+/// * `b` Must be less than the modulus.
+/// * `modulus` Must be positive.
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
 /// Determine the number of times each digit appears in the given number.
 ///
 /// * `num`
@@ -218,21 +365,267 @@ pub fn isqrt(mut num: i64) -> i64 {
     result
 }
 
+/// Count the prime numbers less than or equal to the given number using the
+/// Meissel-Lehmer method. The running time is roughly proportional to the
+/// number raised to the power 2/3.
+///
+/// * `num`
+pub fn prime_count(num: i64) -> i64 {
+    meissel_lehmer(num, 0) as i64
+}
+
+/// Calculate the sum of the prime numbers less than or equal to the given
+/// number using the Meissel-Lehmer method. See `prime_count`.
+///
+/// * `num`
+pub fn prime_sum(num: i64) -> i128 {
+    meissel_lehmer(num, 1)
+}
+
+/// Calculate the sum of a power of the prime numbers less than or equal to
+/// the given number, following Lagarias, Miller and Odlyzko. Let `a` be the
+/// number of prime numbers not exceeding `y`, a small multiple of the cube
+/// root of the number. The numbers from 1 to the given number which have no
+/// prime factors among the first `a` prime numbers are 1, the prime numbers
+/// greater than `y`, and the products of two such prime numbers. The sum over
+/// these numbers (a partial sieve function) is expanded into ordinary leaves,
+/// which have closed forms, and special leaves, which are partial sieve
+/// functions of numbers less than the number divided by `y`. Special leaves
+/// are found either from sums over prime numbers or by sieving that range in
+/// segments.
+///
+/// * `num`
+/// * `power` Must be 0 (to count the prime numbers) or 1 (to sum them).
+fn meissel_lehmer(num: i64, power: u32) -> i128 {
+    if num < 1000000 {
+        return SieveOfAtkin::new(num.max(0) as usize)
+            .iter()
+            .map(|prime| (prime as i128).pow(power))
+            .sum();
+    }
+    let mut cbrt = (num as f64).cbrt() as i64;
+    while cbrt * cbrt * cbrt > num {
+        cbrt -= 1;
+    }
+    while (cbrt + 1).checked_pow(3).is_some_and(|cube| cube <= num) {
+        cbrt += 1;
+    }
+    // A larger multiple of the cube root shrinks the range to sieve, but
+    // increases the number of special leaves.
+    let y = cbrt * 4;
+    let limit = num / y;
+
+    // Sums over prime numbers up to the sieve limit. For counts, the sieve
+    // answers directly; for sums, keep a running total at each prime number.
+    let sieve_of_atkin = SieveOfAtkin::new(limit as usize);
+    let prime_sums = if power == 0 {
+        vec![]
+    } else {
+        let mut sum = 0u64;
+        sieve_of_atkin
+            .iter()
+            .map(|prime| {
+                sum = sum.checked_add(prime as u64).expect("overflow detected");
+                sum
+            })
+            .collect()
+    };
+    let prime_sum = |num: i64| match (sieve_of_atkin.rank(num as usize), power) {
+        (rank, 0) => rank as i128,
+        (0, _) => 0,
+        (rank, _) => prime_sums[rank - 1] as i128,
+    };
+    let primes = sieve_of_atkin
+        .iter()
+        .take_while(|&prime| prime <= y)
+        .collect::<Vec<i64>>();
+    let linear_sieve = LinearSieve::new(y as usize);
+    let mobius = linear_sieve.mobius();
+    let weight = |num: i64| (num as i128).pow(power);
+    let total = |num: i64| match power {
+        0 => num as i128,
+        _ => num as i128 * (num as i128 + 1) / 2,
+    };
+
+    // Ordinary leaves: squarefree numbers up to `y`.
+    let mut phi = (1..=y)
+        .filter(|&n| mobius[n as usize] != 0)
+        .map(|n| mobius[n as usize] as i128 * weight(n) * total(num / n))
+        .sum::<i128>();
+
+    // Special leaves: squarefree products of a prime number `prime` and a
+    // number `n` which does not exceed `y` and whose prime factors are
+    // greater than `prime`, such that the product exceeds `y`. Each
+    // contributes the partial sieve function of `num / (n * prime)` using
+    // the prime numbers less than `prime`. If that is less than the square of
+    // `prime`, only 1 and prime numbers are left, so the value is obtained
+    // from sums over prime numbers. Otherwise, it is found by sieving.
+    let coprime =
+        |n: i64, prime: i64| mobius[n as usize] != 0 && linear_sieve.smallest_prime_factor(n as usize) > Some(prime);
+    for &prime in &primes {
+        let lower = (y / prime).max(prime).max(num / prime / prime / prime);
+        let easy = |n: i64| {
+            // If the leaf is less than `prime`, only 1 is left.
+            let leaf = (num / (n * prime)).max(prime - 1);
+            mobius[n as usize] as i128 * weight(n * prime) * (prime_sum(leaf) - prime_sum(prime - 1) + 1)
+        };
+        if prime * prime > y {
+            // The remaining factor must be prime.
+            phi -= primes[primes.partition_point(|&n| n <= lower)..]
+                .iter()
+                .map(|&n| easy(n))
+                .sum::<i128>();
+        } else {
+            phi -= (lower + 1..=y).filter(|&n| coprime(n, prime)).map(easy).sum::<i128>();
+        }
+    }
+    let hard_primes = &primes[..primes.partition_point(|&prime| prime <= cbrt)];
+    // Partial sieve functions of the number preceding the current segment.
+    let mut carries = vec![0i128; hard_primes.len()];
+    let mut low = 1;
+    while low <= limit {
+        let mut segment = PartialSieveSegment::new(low, MEISSEL_LEHMER_SEGMENT_LEN, power);
+        let high = low + MEISSEL_LEHMER_SEGMENT_LEN as i64;
+        for (&prime, carry) in hard_primes.iter().zip(carries.iter_mut()) {
+            let lower = (y / prime).max(prime).max(num / prime / high);
+            let upper = y.min(num / prime / low).min(num / prime / prime / prime);
+            for n in (lower + 1..=upper).filter(|&n| coprime(n, prime)) {
+                let leaf = *carry + segment.sum(num / (n * prime)) as i128;
+                phi -= mobius[n as usize] as i128 * weight(n * prime) * leaf;
+            }
+            *carry += segment.sum(high - 1) as i128;
+            for multiple in ((low + prime - 1) / prime * prime..high).step_by(prime as usize) {
+                segment.remove(multiple);
+            }
+        }
+        low = high;
+    }
+
+    // Remove the products of two prime numbers greater than `y`.
+    let products = sieve_of_atkin
+        .iter()
+        .skip(primes.len())
+        .take_while(|&prime| prime * prime <= num)
+        .map(|prime| weight(prime) * (prime_sum(num / prime) - prime_sum(prime) + weight(prime)))
+        .sum::<i128>();
+    phi + prime_sum(y) - 1 - products
+}
+
+// Number of numbers in each segment sieved to find the special leaves in the
+// Meissel-Lehmer method. Must be a multiple of 64.
+const MEISSEL_LEHMER_SEGMENT_LEN: usize = 1 << 18;
+
+/// Range of consecutive numbers from which multiples of prime numbers are
+/// removed one prime number at a time, with a Fenwick tree over the words of
+/// the bitfield so that sums over the remaining numbers in any prefix of the
+/// range can be found while sieving.
+struct PartialSieveSegment {
+    low: i64,
+    power: u32,
+    bitfield: Vec<u64>,
+    fenwick_tree: Vec<u64>,
+}
+
+impl PartialSieveSegment {
+    /// Construct a segment in which no number has been removed.
+    ///
+    /// * `low` Smallest number in the segment.
+    /// * `len` Number of numbers in the segment. Must be a multiple of 64.
+    /// * `power` Power of each number to sum over.
+    fn new(low: i64, len: usize, power: u32) -> PartialSieveSegment {
+        let words = len / 64;
+        let mut fenwick_tree = (0..words as i64)
+            .map(|word| match power {
+                0 => 64,
+                _ => 64 * (low + word * 64) as u64 + 2016,
+            })
+            .collect::<Vec<u64>>();
+        for idx in 0..words {
+            let parent = idx | (idx + 1);
+            if parent < words {
+                fenwick_tree[parent] += fenwick_tree[idx];
+            }
+        }
+        PartialSieveSegment {
+            low,
+            power,
+            bitfield: vec![u64::MAX; words],
+            fenwick_tree,
+        }
+    }
+
+    /// Remove a number from the segment, if it was not already removed.
+    ///
+    /// * `num` Must be in the segment.
+    fn remove(&mut self, num: i64) {
+        let offset = (num - self.low) as usize;
+        let (word, bit) = (offset / 64, offset % 64);
+        if self.bitfield[word] >> bit & 1 == 0 {
+            return;
+        }
+        self.bitfield[word] &= !(1 << bit);
+        let weight = (num as u64).pow(self.power);
+        let mut idx = word;
+        while idx < self.fenwick_tree.len() {
+            self.fenwick_tree[idx] -= weight;
+            idx |= idx + 1;
+        }
+    }
+
+    /// Calculate the sum of a power of the numbers remaining in the segment
+    /// which do not exceed the given number.
+    ///
+    /// * `num` Must be in the segment.
+    fn sum(&self, num: i64) -> u64 {
+        let offset = (num - self.low) as usize;
+        let (word, bit) = (offset / 64, offset % 64);
+        let mut sum = 0;
+        let mut idx = word;
+        while idx > 0 {
+            sum += self.fenwick_tree[idx - 1];
+            idx &= idx - 1;
+        }
+        let mut bitfield = self.bitfield[word] & (u64::MAX >> (63 - bit));
+        if self.power == 0 {
+            return sum + bitfield.count_ones() as u64;
+        }
+        while bitfield != 0 {
+            sum += self.low as u64 + (word * 64) as u64 + bitfield.trailing_zeros() as u64;
+            bitfield &= bitfield - 1;
+        }
+        sum
+    }
+}
+
 mod objects;
+pub use objects::big_int::BigInt;
+pub use objects::decimal_expansion::DecimalExpansion;
 pub use objects::fraction::Fraction;
+pub use objects::linear_sieve::LinearSieve;
 pub use objects::long::Long;
+pub use objects::long::ParseLongError;
+pub use objects::long::TryFromLongError;
 pub use objects::pandigital_checker::PandigitalChecker;
 pub use objects::poker_hand::PokerHand;
+pub use objects::rational::Rational;
+pub use objects::sieve_of_atkin::SegmentedSieve;
+pub use objects::sieve_of_atkin::SieveError;
 pub use objects::sieve_of_atkin::SieveOfAtkin;
+pub use objects::sieve_of_atkin::SieveView;
+pub use objects::stern_brocot::SternBrocot;
 
 mod iterators;
 pub use iterators::bits::Bits;
 pub use iterators::collatz::Collatz;
 pub use iterators::continued_fraction::ContinuedFraction;
+pub use iterators::convergents::ConvergentInteger;
+pub use iterators::convergents::Convergents;
 pub use iterators::cubes::Cubes;
 pub use iterators::digits::Digits;
 pub use iterators::divisors::Divisors;
+pub use iterators::farey::Farey;
 pub use iterators::fibonacci::Fibonacci;
+pub use iterators::pell::Pell;
 pub use iterators::polygonal::Polygonal;
 pub use iterators::potential_primes::PotentialPrimes;
 pub use iterators::pythagorean_triplets::PythagoreanTriplets;
@@ -305,6 +698,347 @@ mod tests {
         assert_eq!(utils::isqrt(2i64.pow(54) - 1), 134217727);
     }
 
+    #[test]
+    fn prime_count_test() {
+        let sieve_of_atkin = utils::SieveOfAtkin::new(100000);
+        let (mut count, mut sum) = (0, 0);
+        for num in -1..=100000 {
+            if num > 0 && sieve_of_atkin.is_prime(num as usize) {
+                count += 1;
+                sum += num as i128;
+            }
+            if num < 2000 || num % 997 == 0 {
+                assert_eq!(utils::prime_count(num), count);
+                assert_eq!(utils::prime_sum(num), sum);
+            }
+        }
+        let sieve_of_atkin = utils::SieveOfAtkin::new(3000000);
+        let (mut count, mut sum) = (0, 0);
+        for num in 0..=3000000 {
+            if sieve_of_atkin.is_prime(num as usize) {
+                count += 1;
+                sum += num as i128;
+            }
+            if num >= 999000 && (num < 1001000 || num % 9973 == 0) {
+                assert_eq!(utils::prime_count(num), count);
+                assert_eq!(utils::prime_sum(num), sum);
+            }
+        }
+        assert_eq!(utils::prime_count(10i64.pow(10)), 455052511);
+        assert_eq!(utils::prime_sum(10i64.pow(10)), 2220822432581729238);
+        assert_eq!(utils::prime_count(10i64.pow(11)), 4118054813);
+        assert_eq!(utils::prime_sum(10i64.pow(11)), 201467077743744681014);
+        assert_eq!(utils::prime_count(10i64.pow(12)), 37607912018);
+        assert_eq!(utils::prime_sum(10i64.pow(12)), 18435588552550705911377);
+        assert_eq!(utils::prime_count(10i64.pow(13)), 346065536839);
+        assert_eq!(utils::prime_sum(10i64.pow(13)), 1699246443377779418889494);
+    }
+
+    #[test]
+    fn sieve_of_atkin_rank_test() {
+        for limit in [0, 1, 2, 4, 5, 59, 60, 61, 1000, 123457] {
+            let sieve_of_atkin = utils::SieveOfAtkin::new(limit);
+            let primes = sieve_of_atkin.iter().collect::<Vec<i64>>();
+            for (idx, &prime) in primes.iter().enumerate() {
+                assert_eq!(sieve_of_atkin.nth_prime(idx), Some(prime));
+            }
+            assert_eq!(sieve_of_atkin.nth_prime(primes.len()), None);
+            for num in 0..=limit {
+                let rank = primes.partition_point(|&prime| prime <= num as i64);
+                assert_eq!(sieve_of_atkin.rank(num), rank);
+                assert_eq!(sieve_of_atkin.next_prime(num), primes.get(rank).copied());
+                let rank = primes.partition_point(|&prime| prime < num as i64);
+                assert_eq!(
+                    sieve_of_atkin.prev_prime(num),
+                    rank.checked_sub(1).map(|rank| primes[rank])
+                );
+            }
+            assert_eq!(sieve_of_atkin.next_prime(limit + 1), None);
+            assert_eq!(sieve_of_atkin.prev_prime(limit + 1), primes.last().copied());
+            assert_eq!(sieve_of_atkin.prev_prime(limit + 2), None);
+            assert_eq!(sieve_of_atkin.prev_prime(usize::MAX), None);
+            for (lo, hi) in [
+                (0, limit),
+                (0, 0),
+                (3, 3),
+                (4, 100),
+                (59, 61),
+                (1000, 2000),
+                (limit, limit + 100),
+            ] {
+                let expected = primes
+                    .iter()
+                    .copied()
+                    .filter(|&prime| lo as i64 <= prime && prime <= hi as i64);
+                assert!(sieve_of_atkin.iter_range(lo..=hi).eq(expected.clone()));
+                assert!(sieve_of_atkin.iter_range(lo..=hi).rev().eq(expected.clone().rev()));
+                assert!(sieve_of_atkin.iter_range(lo..hi + 1).eq(expected));
+            }
+            assert!(sieve_of_atkin.iter_range(..).eq(primes.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn sieve_of_atkin_checked_test() {
+        assert_eq!(
+            utils::SieveOfAtkin::try_new(usize::MAX).err(),
+            Some(utils::SieveError::Overflow)
+        );
+        assert_eq!(
+            utils::SieveOfAtkin::try_new(i64::MAX as usize).err(),
+            Some(utils::SieveError::Overflow)
+        );
+        // Smallest limit which, when rounded, exceeds `i64::MAX`.
+        assert_eq!(
+            utils::SieveOfAtkin::try_new(i64::MAX as usize - i64::MAX as usize % 60).err(),
+            Some(utils::SieveError::Overflow)
+        );
+        assert_eq!(
+            utils::SieveOfAtkin::try_new_parallel(usize::MAX, None).err(),
+            Some(utils::SieveError::Overflow)
+        );
+        for limit in [0, 1, 59, 60, 1000] {
+            let sieve_of_atkin = utils::SieveOfAtkin::try_new(limit).unwrap();
+            assert!(utils::SieveOfAtkin::try_new_parallel(limit, Some(2)).unwrap() == sieve_of_atkin);
+            assert_eq!(sieve_of_atkin.limit(), limit);
+            for num in 0..=limit {
+                assert_eq!(sieve_of_atkin.checked_is_prime(num), Some(utils::is_prime(num as i64)));
+            }
+            let out_of_range = limit - limit % 60 + 60;
+            assert_eq!(sieve_of_atkin.checked_is_prime(out_of_range), None);
+            let sieve_of_atkin = sieve_of_atkin.with_fallback(true);
+            for num in out_of_range..out_of_range + 1000 {
+                assert_eq!(sieve_of_atkin.checked_is_prime(num), Some(utils::is_prime(num as i64)));
+                assert_eq!(sieve_of_atkin.is_prime(num), utils::is_prime(num as i64));
+            }
+            assert_eq!(sieve_of_atkin.checked_is_prime(usize::MAX), None);
+        }
+    }
+
+    #[test]
+    fn sieve_of_atkin_save_load_test() {
+        let path = std::env::temp_dir().join(format!("sieve_of_atkin_save_load_test_{}.bin", std::process::id()));
+        for limit in [0, 1, 59, 60, 61, 123456] {
+            let sieve_of_atkin = utils::SieveOfAtkin::new(limit);
+            sieve_of_atkin.save(&path).unwrap();
+            let loaded = utils::SieveOfAtkin::load(&path).unwrap();
+            assert_eq!(loaded.limit(), limit);
+            assert!(loaded.iter().eq(sieve_of_atkin.iter()));
+            assert_eq!(loaded.rank(limit), sieve_of_atkin.rank(limit));
+
+            let bytes = std::fs::read(&path).unwrap();
+            let view = utils::SieveView::new(&bytes).unwrap();
+            assert_eq!(view.limit(), limit);
+            assert!(view.iter().eq(sieve_of_atkin.iter()));
+            for num in 0..=limit {
+                assert_eq!(view.is_prime(num), sieve_of_atkin.is_prime(num));
+            }
+            assert_eq!(view.checked_is_prime(limit - limit % 60 + 60), None);
+        }
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            utils::SieveOfAtkin::load(&path).err(),
+            Some(utils::SieveError::Io(std::io::ErrorKind::NotFound))
+        );
+
+        let bytes = utils::SieveOfAtkin::new(1000).to_bytes();
+        let corrupt = |idx: usize, byte: u8| {
+            let mut bytes = bytes.clone();
+            bytes[idx] = byte;
+            utils::SieveOfAtkin::from_bytes(&bytes).err()
+        };
+        assert_eq!(corrupt(0, b'X'), Some(utils::SieveError::InvalidMagic));
+        assert_eq!(corrupt(4, 2), Some(utils::SieveError::UnsupportedVersion(2)));
+        assert_eq!(corrupt(8, 0), Some(utils::SieveError::LengthMismatch));
+        assert_eq!(corrupt(15, 0xff), Some(utils::SieveError::Overflow));
+        assert_eq!(corrupt(16, bytes[16] ^ 1), Some(utils::SieveError::ChecksumMismatch));
+        assert_eq!(corrupt(30, bytes[30] ^ 1), Some(utils::SieveError::ChecksumMismatch));
+        for len in [0, 10, 24, bytes.len() - 1] {
+            assert_eq!(
+                utils::SieveView::new(&bytes[..len]).err(),
+                Some(utils::SieveError::LengthMismatch)
+            );
+        }
+        let mut extended = bytes.clone();
+        extended.extend([0, 0]);
+        assert_eq!(
+            utils::SieveView::new(&extended).err(),
+            Some(utils::SieveError::LengthMismatch)
+        );
+    }
+
+    #[test]
+    fn linear_sieve_test() {
+        let linear_sieve = utils::LinearSieve::new(5000);
+        assert!(linear_sieve.iter().eq(utils::SieveOfAtkin::new(5000).iter()));
+        let totients = linear_sieve.totients();
+        let mobius = linear_sieve.mobius();
+        let omega = linear_sieve.omega();
+        let big_omega = linear_sieve.big_omega();
+        let divisor_counts = linear_sieve.divisor_counts();
+        let divisor_sums = [0, 1, 2, 3].map(|k| linear_sieve.divisor_sums(k));
+        for num in 1..=5000 {
+            let factors = linear_sieve.factorise(num).collect::<Vec<(i64, u32)>>();
+            assert!(factors.windows(2).all(|window| window[0].0 < window[1].0));
+            assert!(factors.iter().all(|&(prime, _)| utils::is_prime(prime)));
+            assert_eq!(
+                factors
+                    .iter()
+                    .map(|&(prime, exponent)| prime.pow(exponent))
+                    .product::<i64>(),
+                num as i64
+            );
+            assert_eq!(linear_sieve.is_prime(num), utils::is_prime(num as i64));
+            assert_eq!(
+                linear_sieve.smallest_prime_factor(num),
+                factors.first().map(|&(prime, _)| prime)
+            );
+
+            let totient = (1..=num as i64).filter(|&k| utils::gcd(k, num as i64) == 1).count();
+            assert_eq!(totients[num], totient as i64);
+            let square_free = factors.iter().all(|&(_, exponent)| exponent == 1);
+            let expected_mobius = if square_free {
+                (-1i8).pow(factors.len() as u32)
+            } else {
+                0
+            };
+            assert_eq!(mobius[num], expected_mobius);
+            assert_eq!(omega[num] as usize, factors.len());
+            assert_eq!(
+                big_omega[num] as u32,
+                factors.iter().map(|&(_, exponent)| exponent).sum::<u32>()
+            );
+            let divisors = utils::Divisors::new(num as i64).collect::<Vec<i64>>();
+            assert_eq!(divisor_counts[num] as usize, divisors.len());
+            for k in 0..4 {
+                let sum = divisors.iter().map(|&divisor| (divisor as u64).pow(k)).sum::<u64>();
+                assert_eq!(divisor_sums[k as usize][num], sum);
+            }
+        }
+    }
+
+    #[test]
+    fn factorise_test() {
+        let linear_sieve = utils::LinearSieve::new(100000);
+        for num in 1..=100000 {
+            assert!(utils::factorise(num as i64).into_iter().eq(linear_sieve.factorise(num)));
+        }
+
+        // Semiprimes near 2.pow(63) whose factors are near its square root.
+        let primes = (0..3037000499i64)
+            .rev()
+            .filter(|&num| utils::is_prime(num))
+            .take(5)
+            .collect::<Vec<i64>>();
+        for (idx, &p) in primes.iter().enumerate() {
+            for &q in &primes[idx..] {
+                let expected = if p == q { vec![(p, 2)] } else { vec![(q, 1), (p, 1)] };
+                assert_eq!(utils::factorise(p * q), expected);
+            }
+        }
+        assert_eq!(
+            utils::factorise(3 * 3074457345618258599),
+            vec![(3, 1), (3074457345618258599, 1)]
+        );
+        assert_eq!(utils::factorise(1 << 62), vec![(2, 62)]);
+        assert_eq!(
+            utils::factorise(i64::MAX),
+            vec![(7, 2), (73, 1), (127, 1), (337, 1), (92737, 1), (649657, 1)]
+        );
+        for num in i64::MAX - 1000..=i64::MAX {
+            let factors = utils::factorise(num);
+            assert!(factors.windows(2).all(|window| window[0].0 < window[1].0));
+            assert!(factors.iter().all(|&(prime, _)| utils::is_prime(prime)));
+            assert_eq!(
+                factors
+                    .iter()
+                    .map(|&(prime, exponent)| (prime as i128).pow(exponent))
+                    .product::<i128>(),
+                num as i128
+            );
+        }
+    }
+
+    #[test]
+    fn factorise_u64_test() {
+        // Semiprimes near 2.pow(64) whose factors are near its square root.
+        let primes = (0..1u64 << 32)
+            .rev()
+            .filter(|&num| utils::is_prime(num as i64))
+            .take(5)
+            .collect::<Vec<u64>>();
+        for (idx, &p) in primes.iter().enumerate() {
+            for &q in &primes[idx..] {
+                let expected = if p == q { vec![(p, 2)] } else { vec![(q, 1), (p, 1)] };
+                assert_eq!(utils::factorise_u64(p * q), expected);
+            }
+        }
+        assert_eq!(
+            utils::factorise_u64(18446744073709551557),
+            vec![(18446744073709551557, 1)]
+        );
+        assert_eq!(utils::factorise_u64(1 << 63), vec![(2, 63)]);
+        assert_eq!(
+            utils::factorise_u64(u64::MAX),
+            vec![(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65537, 1), (6700417, 1)]
+        );
+        for num in (u64::MAX - 1000..=u64::MAX).chain(i64::MAX as u64 - 100..=i64::MAX as u64 + 100) {
+            let factors = utils::factorise_u64(num);
+            assert!(factors.windows(2).all(|window| window[0].0 < window[1].0));
+            assert!(factors
+                .iter()
+                .all(|&(prime, _)| utils::Long::from(prime).is_probable_prime()));
+            assert_eq!(
+                factors
+                    .iter()
+                    .map(|&(prime, exponent)| (prime as u128).pow(exponent))
+                    .product::<u128>(),
+                num as u128
+            );
+        }
+    }
+
+    #[test]
+    fn sieve_of_atkin_parallel_test() {
+        for limit in [0, 1, 59, 60, 61, 1000, 123456, 10000000] {
+            let sieve_of_atkin = utils::SieveOfAtkin::new(limit);
+            for threads in [1, 2, 3, 7, 64] {
+                let sieve_of_atkin_parallel = utils::SieveOfAtkin::new_parallel(limit, Some(threads));
+                // The bitfields must be identical, not just the primes
+                // generated from them.
+                assert!(sieve_of_atkin_parallel == sieve_of_atkin);
+            }
+        }
+    }
+
+    #[test]
+    fn segmented_sieve_test() {
+        let sieve_of_atkin = utils::SieveOfAtkin::new(3000000);
+        for (lo, hi) in [
+            (0, 0),
+            (0, 1),
+            (0, 100),
+            (2, 5),
+            (4, 60),
+            (59, 61),
+            (1000, 3000000),
+            (1234567, 2345678),
+        ] {
+            let segmented_sieve = utils::SegmentedSieve::new(lo, hi);
+            let primes = sieve_of_atkin
+                .iter()
+                .filter(|&prime| lo as i64 <= prime && prime <= hi as i64);
+            assert!(segmented_sieve.iter().eq(primes));
+            for num in lo..=hi.min(lo + 10000) {
+                assert_eq!(segmented_sieve.is_prime(num), sieve_of_atkin.is_prime(num));
+            }
+        }
+        let (lo, hi) = (10usize.pow(12), 10usize.pow(12) + 100000);
+        let segmented_sieve = utils::SegmentedSieve::new(lo, hi);
+        let primes = (lo as i64..=hi as i64).filter(|&num| utils::is_prime(num));
+        assert!(segmented_sieve.iter().eq(primes));
+    }
+
     #[test]
     fn long_arithmetic_test() {
         for line in lines("res/tests/long_arithmetic_test.txt") {
@@ -355,6 +1089,535 @@ mod tests {
         }
     }
 
+    #[test]
+    fn long_multiplication_strategies_test() {
+        // Operands of various lengths, including some long enough for the
+        // number-theoretic transform and some too unbalanced for plain
+        // Karatsuba's method.
+        let operands = [1, 30, 400, 3000, 6000]
+            .into_iter()
+            .map(|exp| utils::Long::new("987654321987654321").pow(exp))
+            .collect::<Vec<utils::Long>>();
+        for a in operands.iter() {
+            for b in operands.iter() {
+                let product = a.mul_schoolbook(b);
+                assert_eq!(a.mul_karatsuba(b), product);
+                assert_eq!(a.mul_ntt(b), product);
+                assert_eq!(a * b, product);
+            }
+            assert_eq!(a.square(), a.mul_schoolbook(a));
+        }
+        assert_eq!(utils::Long::new("2").pow(100000).sum(), 135178);
+        assert_eq!(utils::Long::factorial(20000).sum(), 325494);
+        assert_eq!(
+            (&utils::Long::new("3").pow(123456) * &utils::Long::new("7").pow(54321)).sum(),
+            471879
+        );
+    }
+
+    #[test]
+    fn long_conversion_test() {
+        assert_eq!("".parse::<utils::Long>(), Err(utils::ParseLongError::Empty));
+        assert_eq!("+".parse::<utils::Long>(), Err(utils::ParseLongError::Empty));
+        assert_eq!("12a4".parse::<utils::Long>(), Err(utils::ParseLongError::InvalidDigit));
+        assert_eq!("-1".parse::<utils::Long>(), Err(utils::ParseLongError::Negative));
+        assert_eq!("-+1".parse::<utils::BigInt>(), Err(utils::ParseLongError::InvalidDigit));
+        assert_eq!("+0001".parse::<utils::Long>(), Ok(utils::Long::from(1u32)));
+        for num in [
+            0,
+            1,
+            999_999_999,
+            1_000_000_000,
+            u32::MAX as u128,
+            u64::MAX as u128,
+            u128::MAX,
+        ] {
+            let long = utils::Long::from(num);
+            assert_eq!(long.to_string(), num.to_string());
+            assert_eq!(u128::try_from(&long), Ok(num));
+            assert_eq!(u64::try_from(&long).ok(), u64::try_from(num).ok());
+            assert_eq!(i64::try_from(&long).ok(), i64::try_from(num).ok());
+        }
+        assert_eq!(
+            u128::try_from(&(&utils::Long::from(u128::MAX) + &utils::Long::from(1u32))),
+            Err(utils::TryFromLongError)
+        );
+        let long = utils::Long::new("12345678901234567890123456789");
+        assert_eq!(long.to_f64(), 1.2345678901234568e28);
+        assert!((long.log10() - 28.091514977).abs() < 1e-9);
+        assert!((utils::Long::new("7").pow(100000).log10() - 84509.804001).abs() < 1e-6);
+        assert!(utils::Long::new("7").pow(100000).to_f64().is_infinite());
+    }
+
+    #[test]
+    fn long_ordering_test() {
+        let mut longs = lines("res/tests/long_subtraction_test.txt")
+            .flat_map(|line| {
+                line.split_ascii_whitespace()
+                    .map(utils::Long::new)
+                    .collect::<Vec<utils::Long>>()
+            })
+            .collect::<Vec<utils::Long>>();
+        longs.sort();
+        for pair in longs.windows(2) {
+            assert!(pair[1].checked_sub(&pair[0]).is_some());
+            assert_eq!(pair[0].cmp(&pair[1]) == std::cmp::Ordering::Equal, pair[0] == pair[1]);
+        }
+
+        // Distinct powers, as in problem 29.
+        let powers = (2..=100u32)
+            .flat_map(|a| (2..=100).map(move |b| utils::Long::from(a).pow(b)))
+            .collect::<Vec<utils::Long>>();
+        assert_eq!(powers.iter().collect::<std::collections::HashSet<_>>().len(), 9183);
+        assert_eq!(powers.iter().collect::<std::collections::BTreeSet<_>>().len(), 9183);
+
+        assert_eq!(utils::Long::default(), utils::Long::zero());
+        let longs = (1..=100u32).map(utils::Long::from).collect::<Vec<utils::Long>>();
+        assert_eq!(longs.iter().sum::<utils::Long>(), utils::Long::from(5050u32));
+        assert_eq!(longs.iter().product::<utils::Long>(), utils::Long::factorial(100));
+        assert_eq!(
+            longs.into_iter().rev().product::<utils::Long>(),
+            utils::Long::factorial(100)
+        );
+        assert_eq!(
+            std::iter::empty::<utils::Long>().product::<utils::Long>(),
+            utils::Long::one()
+        );
+    }
+
+    #[test]
+    fn long_subtraction_test() {
+        for line in lines("res/tests/long_subtraction_test.txt") {
+            let [minuend, subtrahend, difference]: [utils::Long; 3] = line
+                .split_ascii_whitespace()
+                .map(utils::Long::new)
+                .collect::<Vec<utils::Long>>()
+                .try_into()
+                .unwrap();
+            assert_eq!(&minuend - &subtrahend, difference);
+            assert_eq!(&difference + &subtrahend, minuend);
+            assert_eq!(subtrahend.checked_sub(&minuend).is_some(), subtrahend == minuend);
+        }
+    }
+
+    #[test]
+    fn long_division_test() {
+        for line in lines("res/tests/long_division_test.txt") {
+            let [dividend, divisor, quotient, rem]: [utils::Long; 4] = line
+                .split_ascii_whitespace()
+                .map(utils::Long::new)
+                .collect::<Vec<utils::Long>>()
+                .try_into()
+                .unwrap();
+            assert_eq!(dividend.div_rem(&divisor), (quotient.clone(), rem.clone()));
+            if let Ok(divisor) = divisor.to_string().parse::<u32>() {
+                assert_eq!(&dividend / divisor, quotient);
+                assert_eq!(&dividend % divisor, rem);
+            }
+        }
+        assert!(utils::Long::new("1").checked_div_rem(&utils::Long::new("0")).is_none());
+    }
+
+    #[test]
+    fn big_int_arithmetic_test() {
+        for line in lines("res/tests/big_int_arithmetic_test.txt") {
+            let [a, b, sum, difference, product, quotient, rem]: [utils::BigInt; 7] = line
+                .split_ascii_whitespace()
+                .map(utils::BigInt::new)
+                .collect::<Vec<utils::BigInt>>()
+                .try_into()
+                .unwrap();
+            assert_eq!(&a + &b, sum);
+            assert_eq!(&a - &b, difference);
+            assert_eq!(&b - &a, -&difference);
+            assert_eq!(&a * &b, product);
+            assert_eq!(a.div_rem(&b), (quotient, rem));
+            assert_eq!(a.to_string(), line.split_ascii_whitespace().next().unwrap());
+            assert_eq!((-&a).abs(), a.abs());
+        }
+    }
+
+    #[test]
+    fn fraction_arithmetic_test() {
+        let parse = |s: &str| {
+            let (numerator, denominator) = s.split_once('/').unwrap();
+            utils::Fraction::new(utils::BigInt::new(numerator), utils::BigInt::new(denominator))
+        };
+        for line in lines("res/tests/fraction_arithmetic_test.txt") {
+            let [a, b, sum, difference, product, quotient]: [utils::Fraction; 6] = line
+                .split_ascii_whitespace()
+                .map(parse)
+                .collect::<Vec<utils::Fraction>>()
+                .try_into()
+                .unwrap();
+            let mut result = &a + &b;
+            assert_eq!(result, sum);
+            result.reduce();
+            assert_eq!(result.to_string(), sum.to_string());
+            assert_eq!(&a - &b, difference);
+            assert_eq!(&b - &a, -&difference);
+            assert_eq!(&a * &b, product);
+            assert_eq!(&a / &b, quotient);
+            assert_eq!(&(&a + 3) - 3, a);
+            assert_eq!(&(&a * 7) / 7, a);
+            assert_eq!(&a + -7, &a - 7);
+            assert_eq!(&a * -1, -&a);
+            assert_eq!(&(&a - i64::MIN) + i64::MIN, a);
+            assert_eq!(&(&a / -7) * -7, a);
+            let integer = b.floor();
+            let fraction: utils::Fraction = integer.clone().into();
+            assert_eq!(&a + &integer, &a + &fraction);
+            assert_eq!(&a - &integer, &a - &fraction);
+            assert_eq!(&a * &integer, &a * &fraction);
+            if !integer.is_zero() {
+                assert_eq!(&a / &integer, &a / &fraction);
+            }
+            assert_eq!(a < b, (&a - &b).numerator().is_negative());
+        }
+    }
+
+    #[test]
+    fn rational_arithmetic_test() {
+        let parse = |s: &str| {
+            let (numerator, denominator) = s.split_once('/')?;
+            Some(utils::Rational::new(
+                numerator.parse().unwrap(),
+                denominator.parse().unwrap(),
+            ))
+        };
+        for line in lines("res/tests/rational_arithmetic_test.txt") {
+            let tokens = line.split_ascii_whitespace().collect::<Vec<&str>>();
+            let [a, b, sum, difference, product, quotient, mediant]: [Option<utils::Rational>; 7] = tokens[..7]
+                .iter()
+                .map(|token| parse(token))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap();
+            let (a, b) = (a.unwrap(), b.unwrap());
+            assert_eq!(a.checked_add(&b), sum);
+            assert_eq!(a.checked_sub(&b), difference);
+            assert_eq!(a.checked_mul(&b), product);
+            assert_eq!(a.checked_div(&b), quotient);
+            assert_eq!(a.mediant(&b), mediant);
+            let [floor, ceil, round]: [i64; 3] = tokens[7..]
+                .iter()
+                .map(|token| token.parse().unwrap())
+                .collect::<Vec<i64>>()
+                .try_into()
+                .unwrap();
+            assert_eq!((a.floor(), a.ceil(), a.round()), (floor, ceil, round));
+            let (fa, fb): (utils::Fraction, utils::Fraction) = (a.into(), b.into());
+            assert_eq!(a.cmp(&b), fa.cmp(&fb));
+            assert_eq!(fa.to_string(), a.to_string());
+        }
+        let fraction = |numerator: i64, denominator: i64| {
+            utils::Fraction::new(utils::BigInt::from(numerator), utils::BigInt::from(denominator))
+        };
+        assert_eq!(
+            utils::Rational::try_from(&fraction(i64::MIN, 1)),
+            Ok(utils::Rational::new(i64::MIN, 1))
+        );
+        assert_eq!(
+            utils::Rational::try_from(&fraction(i64::MIN, 2)),
+            Ok(utils::Rational::new(i64::MIN / 2, 1))
+        );
+        assert_eq!(
+            utils::Rational::try_from(&fraction(i64::MIN, -1)),
+            Err(utils::TryFromLongError)
+        );
+        assert_eq!(
+            utils::Rational::try_from(&fraction(i64::MAX, i64::MAX)),
+            Ok(utils::Rational::new(1, 1))
+        );
+        let fraction = utils::Fraction::new(
+            utils::BigInt::new("4722366482869645213696"),
+            utils::BigInt::new("1180591620717411303424"),
+        );
+        assert_eq!(utils::Rational::try_from(&fraction), Ok(utils::Rational::new(4, 1)));
+        let fraction = utils::Fraction::new(utils::BigInt::new("-4722366482869645213696"), utils::BigInt::new("-3"));
+        assert_eq!(utils::Rational::try_from(&fraction), Err(utils::TryFromLongError));
+    }
+
+    #[test]
+    fn stern_brocot_test() {
+        let parse = |s: &str| {
+            let (numerator, denominator) = s.split_once('/').unwrap();
+            utils::Rational::new(numerator.parse().unwrap(), denominator.parse().unwrap())
+        };
+        for line in lines("res/tests/stern_brocot_test.txt") {
+            let tokens = line.split_ascii_whitespace().collect::<Vec<&str>>();
+            let (num, max_denominator) = (parse(tokens[0]), tokens[1].parse().unwrap());
+            let (left, right, best) = (parse(tokens[2]), parse(tokens[3]), parse(tokens[4]));
+            assert_eq!(num.farey_neighbours(max_denominator), (left, right));
+            assert_eq!(num.best_approximation(max_denominator), Ok(best));
+            let fraction: utils::Fraction = num.into();
+            let max_denominator = utils::Long::from(max_denominator as u32);
+            assert_eq!(fraction.farey_neighbours(&max_denominator), (left.into(), right.into()));
+            match tokens[5] {
+                "-" => (),
+                path => {
+                    let path = path.trim_matches('.');
+                    let stern_brocot = utils::SternBrocot::find(&fraction);
+                    assert_eq!(stern_brocot.path(), path);
+                    assert_eq!(utils::SternBrocot::from_path(path).unwrap().node(), fraction);
+                }
+            }
+        }
+        assert_eq!(
+            utils::Rational::approximate(std::f64::consts::PI, 100),
+            utils::Rational::new(311, 99)
+        );
+        assert_eq!(
+            utils::Rational::approximate(std::f64::consts::PI, 1000),
+            utils::Rational::new(355, 113)
+        );
+        assert_eq!(
+            utils::Rational::approximate(-0.1, 1000000),
+            utils::Rational::new(-1, 10)
+        );
+        assert_eq!(
+            utils::Rational::approximate(1e-300, 1000000),
+            utils::Rational::new(0, 1)
+        );
+    }
+
+    #[test]
+    fn farey_test() {
+        for order in 1..=100 {
+            let mut fractions = (1..=order)
+                .flat_map(|denominator| {
+                    (0..=denominator).map(move |numerator| utils::Rational::new(numerator, denominator))
+                })
+                .collect::<Vec<utils::Rational>>();
+            fractions.sort();
+            fractions.dedup();
+            assert!(utils::Farey::new(order).eq(fractions));
+        }
+        let parse = |s: &str| {
+            let (numerator, denominator) = s.split_once('/').unwrap();
+            utils::Rational::new(numerator.parse().unwrap(), denominator.parse().unwrap())
+        };
+        for line in lines("res/tests/farey_test.txt") {
+            let tokens = line.split_ascii_whitespace().collect::<Vec<&str>>();
+            let (order, start, end) = (tokens[0].parse().unwrap(), parse(tokens[1]), parse(tokens[2]));
+            let count = tokens[3].parse().unwrap();
+            assert_eq!(utils::Farey::count(order, start, end), count);
+            let fractions = utils::Farey::between(order, start, end).collect::<Vec<utils::Rational>>();
+            assert_eq!(fractions.len() as i64, count);
+            assert!(fractions.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(fractions
+                .iter()
+                .all(|&fraction| start < fraction && fraction < end && fraction.denominator() <= order));
+        }
+    }
+
+    #[test]
+    fn decimal_expansion_test() {
+        for line in lines("res/tests/decimal_expansion_test.txt") {
+            let tokens = line.split_ascii_whitespace().collect::<Vec<&str>>();
+            let (numerator, denominator, radix) = (
+                tokens[0].parse().unwrap(),
+                tokens[1].parse().unwrap(),
+                tokens[2].parse().unwrap(),
+            );
+            let decimal_expansion = utils::DecimalExpansion::new(numerator, denominator, radix);
+            assert_eq!(decimal_expansion.prefix_length(), tokens[3].parse().unwrap());
+            assert_eq!(decimal_expansion.period(), &utils::Long::new(tokens[4]));
+            if tokens[5] != "-" {
+                assert_eq!(decimal_expansion.to_string(), tokens[5]);
+            }
+        }
+    }
+
+    #[test]
+    fn long_gcd_test() {
+        for line in lines("res/tests/long_gcd_test.txt") {
+            let [a, b, g, l]: [utils::Long; 4] = line
+                .split_ascii_whitespace()
+                .map(utils::Long::new)
+                .collect::<Vec<utils::Long>>()
+                .try_into()
+                .unwrap();
+            assert_eq!(a.gcd(&b), g);
+            assert_eq!(b.gcd(&a), g);
+            assert_eq!(a.lcm(&b), l);
+        }
+    }
+
+    #[test]
+    fn long_pow_mod_test() {
+        for line in lines("res/tests/long_pow_mod_test.txt") {
+            let [base, exp, modulus, residue]: [utils::Long; 4] = line
+                .split_ascii_whitespace()
+                .map(utils::Long::new)
+                .collect::<Vec<utils::Long>>()
+                .try_into()
+                .unwrap();
+            assert_eq!(base.pow_mod(&exp, &modulus), residue);
+        }
+    }
+
+    #[test]
+    fn long_root_test() {
+        for line in lines("res/tests/long_root_test.txt") {
+            let mut num_degree_root = line.split_ascii_whitespace();
+            let num = utils::Long::new(num_degree_root.next().unwrap());
+            let degree = num_degree_root.next().unwrap().parse().unwrap();
+            let root = utils::Long::new(num_degree_root.next().unwrap());
+            assert_eq!(num.nth_root(degree), root);
+            if degree == 2 {
+                assert_eq!(num.isqrt(), root);
+            }
+        }
+    }
+
+    #[test]
+    fn long_radix_test() {
+        for line in lines("res/tests/long_radix_test.txt") {
+            let mut num_radix_repr = line.split_ascii_whitespace();
+            let num = utils::Long::new(num_radix_repr.next().unwrap());
+            let radix = num_radix_repr.next().unwrap().parse().unwrap();
+            let repr = num_radix_repr.next().unwrap();
+            assert_eq!(num.to_str_radix(radix), repr);
+            assert_eq!(utils::Long::from_str_radix(repr, radix), Ok(num.clone()));
+            assert_eq!(utils::Long::from_str_radix(&repr.to_uppercase(), radix), Ok(num));
+        }
+        assert_eq!(
+            utils::Long::from_str_radix("102", 2),
+            Err(utils::ParseLongError::InvalidDigit)
+        );
+    }
+
+    #[test]
+    fn long_bitwise_test() {
+        for line in lines("res/tests/long_bitwise_test.txt") {
+            let mut values = line.split_ascii_whitespace();
+            let mut next_long = || utils::Long::new(values.next().unwrap());
+            let [a, b, and, or, xor] = [next_long(), next_long(), next_long(), next_long(), next_long()];
+            let shift = next_long().to_string().parse().unwrap();
+            let [shl, shr, count_ones, bit_length] = [next_long(), next_long(), next_long(), next_long()];
+            assert_eq!(&a & &b, and);
+            assert_eq!(&a | &b, or);
+            assert_eq!(&a ^ &b, xor);
+            assert_eq!(&a << shift, shl);
+            assert_eq!(&a >> shift, shr);
+            assert_eq!(utils::Long::from(a.count_ones()), count_ones);
+            assert_eq!(utils::Long::from(a.bit_length()), bit_length);
+            let bits = a.bits().map(|bit| bit.to_string()).collect::<String>();
+            assert_eq!(
+                bits.chars().rev().collect::<String>(),
+                a.to_str_radix(2).trim_start_matches('0')
+            );
+        }
+    }
+
+    #[test]
+    fn long_digits_test() {
+        for line in lines("res/tests/long_radix_test.txt") {
+            let s = line.split_ascii_whitespace().next().unwrap();
+            let num = utils::Long::new(s);
+            let s = if s == "0" { "" } else { s };
+            let digits = s.bytes().map(|b| (b - b'0') as i64);
+            assert!(num.digits().eq(digits.clone().rev()));
+            assert!(num.digits().rev().eq(digits.clone()));
+            let mut frequency = [0; 10];
+            digits.for_each(|digit| frequency[digit as usize] += 1);
+            assert_eq!(num.digit_frequencies(), frequency);
+            let s_rev = s.chars().rev().collect::<String>();
+            assert_eq!(num.reversed(), utils::Long::new(&format!("0{}", s_rev)));
+            assert_eq!(num.is_palindrome(), s == s_rev);
+            for count in [0, 1, 8, 9, 10, 17, 18, 19, 50] {
+                let count = std::cmp::min(count, s.len());
+                let (left, right) = s.split_at(count);
+                let (left_rev, right_rev) = s.split_at(s.len() - count);
+                assert_eq!(num.leading_digits(count), utils::Long::new(&format!("0{}", left)));
+                assert_eq!(num.trailing_digits(count), utils::Long::new(&format!("0{}", right_rev)));
+                let mut truncated = num.clone();
+                truncated.truncate_left(count);
+                assert_eq!(truncated, utils::Long::new(&format!("0{}", right)));
+                let mut truncated = num.clone();
+                truncated.truncate_right(count);
+                assert_eq!(truncated, utils::Long::new(&format!("0{}", left_rev)));
+            }
+        }
+        assert!(utils::Long::new("12345678987654321").is_palindrome());
+    }
+
+    #[test]
+    fn long_is_prime_test() {
+        for line in lines("res/tests/long_is_prime_test.txt") {
+            let mut num_primality = line.split_ascii_whitespace();
+            let num = utils::Long::new(num_primality.next().unwrap());
+            let primality = num_primality.next().unwrap().parse().unwrap();
+            assert_eq!(num.is_probable_prime(), primality);
+        }
+        assert_eq!(utils::Long::new("1").next_prime(), utils::Long::new("2"));
+        assert_eq!(
+            utils::Long::new("10").pow(30).next_prime(),
+            utils::Long::new("1000000000000000000000000000057")
+        );
+    }
+
+    #[test]
+    fn long_factorise_test() {
+        for line in lines("res/tests/long_factorise_test.txt") {
+            let mut values = line.split_ascii_whitespace();
+            let num = utils::Long::new(values.next().unwrap());
+            let mut factors = vec![];
+            while let Some(prime) = values.next() {
+                factors.push((utils::Long::new(prime), values.next().unwrap().parse().unwrap()));
+            }
+            assert_eq!(num.factorise(), factors);
+        }
+    }
+
+    #[test]
+    fn long_binomial_test() {
+        for line in lines("res/tests/long_binomial_test.txt") {
+            let mut n_k_binomial = line.split_ascii_whitespace();
+            let n = n_k_binomial.next().unwrap().parse().unwrap();
+            let k = n_k_binomial.next().unwrap().parse().unwrap();
+            let binomial = utils::Long::new(n_k_binomial.next().unwrap());
+            assert_eq!(utils::Long::binomial(n, k), binomial);
+        }
+        assert_eq!(utils::Long::binomial(100000, 50000).sum(), 135702);
+    }
+
+    #[test]
+    fn long_sequences_test() {
+        let (mut curr, mut next) = (0u64, 1u64);
+        for n in 0..=90 {
+            assert_eq!(utils::Long::fibonacci(n), utils::Long::from(curr));
+            (curr, next) = (next, curr + next);
+        }
+        assert_eq!(
+            utils::Long::fibonacci(300),
+            utils::Long::new("222232244629420445529739893461909967206666939096499764990979600")
+        );
+        assert_eq!(utils::Long::fibonacci(100000).sum(), 93732);
+        for n in 1..=200 {
+            let lucas = &utils::Long::fibonacci(n - 1) + &utils::Long::fibonacci(n + 1);
+            assert_eq!(utils::Long::lucas(n), lucas);
+        }
+        assert_eq!(utils::Long::lucas(0), utils::Long::from(2u32));
+
+        let catalan = [1u32, 1, 2, 5, 14, 42, 132, 429, 1430, 4862, 16796, 58786];
+        for (n, &value) in catalan.iter().enumerate() {
+            assert_eq!(utils::Long::catalan(n as u32), utils::Long::from(value));
+        }
+        assert_eq!(
+            utils::Long::catalan(100),
+            utils::Long::new("896519947090131496687170070074100632420837521538745909320")
+        );
+
+        assert_eq!(utils::Long::primorial(1), utils::Long::one());
+        assert_eq!(
+            utils::Long::primorial(100),
+            utils::Long::new("2305567963945518424753102147331756070")
+        );
+        assert_eq!(utils::Long::primorial(100000).sum(), 194838);
+    }
+
     #[test]
     fn sieve_of_atkin_smaller_test() {
         let num_of_primes = utils::SieveOfAtkin::new(2usize.pow(14)).iter().count();
@@ -384,4 +1647,82 @@ mod tests {
             assert!(utils::ContinuedFraction::new(num).eq(terms));
         }
     }
+
+    #[test]
+    fn convergents_test() {
+        let expected = [(2, 1), (3, 1), (8, 3), (11, 4), (19, 7), (87, 32), (106, 39), (193, 71)];
+        assert!(utils::ContinuedFraction::e().convergents::<i128>().take(8).eq(expected));
+        for ((numerator, denominator), (expected_numerator, expected_denominator)) in utils::ContinuedFraction::new(2)
+            .convergents::<utils::Long>()
+            .zip(utils::ContinuedFraction::quadratic(0, 2, 1).convergents::<i128>())
+        {
+            assert_eq!(numerator.to_string(), expected_numerator.to_string());
+            assert_eq!(denominator.to_string(), expected_denominator.to_string());
+        }
+    }
+
+    #[test]
+    fn pell_test() {
+        for line in lines("res/tests/pell_test.txt") {
+            let mut tokens = line.split_ascii_whitespace();
+            let d = tokens.next().unwrap().parse().unwrap();
+            let n = tokens.next().unwrap().parse().unwrap();
+            let solutions = tokens
+                .filter(|&token| token != "-")
+                .map(|token| {
+                    let (x, y) = token.split_once(',').unwrap();
+                    (utils::Long::new(x), utils::Long::new(y))
+                })
+                .collect::<Vec<(utils::Long, utils::Long)>>();
+            let mut pell = utils::Pell::new(d, n);
+            for solution in &solutions {
+                assert_eq!(pell.next().as_ref(), Some(solution));
+            }
+            if solutions.is_empty() {
+                // The solutions were searched for only up to a limit.
+                assert!(pell.next().is_none_or(|(_, y)| y >= utils::Long::from(200000u32)));
+            }
+        }
+    }
+
+    #[test]
+    fn pell_fundamental_test() {
+        for d in (2..=10000).filter(|&d: &i64| utils::isqrt(d).pow(2) != d) {
+            let d_long = utils::Long::from(d as u32);
+            let (x, y) = utils::Pell::fundamental(d);
+            assert_eq!(&x * &x, &(&(&y * &y) * &d_long) + &utils::Long::one());
+            if let Some((x, y)) = utils::Pell::fundamental_negative(d) {
+                assert_eq!(&(&x * &x) + &utils::Long::one(), &(&y * &y) * &d_long);
+            }
+        }
+    }
+
+    #[test]
+    fn continued_fraction_quadratic_test() {
+        for line in lines("res/tests/continued_fraction_quadratic_test.txt") {
+            let tokens = line.split_ascii_whitespace().collect::<Vec<&str>>();
+            let [p, d, q, pre_period, period]: [i64; 5] = tokens[..5]
+                .iter()
+                .map(|token| token.parse().unwrap())
+                .collect::<Vec<i64>>()
+                .try_into()
+                .unwrap();
+            let terms = tokens[5].split(',').map(|s| s.parse().unwrap());
+            let continued_fraction = utils::ContinuedFraction::quadratic(p, d, q);
+            assert_eq!(continued_fraction.pre_period(), Some(pre_period as usize));
+            assert_eq!(continued_fraction.period(), Some(period as usize));
+            assert!(continued_fraction.take(40).eq(terms.clone()));
+            if period == 0 {
+                // The last convergent of a rational number is the number
+                // itself.
+                let numerator = p + utils::isqrt(d);
+                let convergents = utils::Convergents::<_, i128>::new(terms).collect::<Vec<(i128, i128)>>();
+                let (last_numerator, last_denominator) = *convergents.last().unwrap();
+                assert_eq!(last_numerator * q as i128, last_denominator * numerator as i128);
+                assert!(utils::ContinuedFraction::rational(numerator, q)
+                    .convergents()
+                    .eq(convergents));
+            }
+        }
+    }
 }
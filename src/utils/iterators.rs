@@ -1,10 +1,13 @@
 pub mod bits;
 pub mod collatz;
 pub mod continued_fraction;
+pub mod convergents;
 pub mod cubes;
 pub mod digits;
 pub mod divisors;
+pub mod farey;
 pub mod fibonacci;
+pub mod pell;
 pub mod polygonal;
 pub mod potential_primes;
 pub mod pythagorean_triplets;
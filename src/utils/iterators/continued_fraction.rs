@@ -1,46 +1,240 @@
 use crate::utils;
 
-/// Generate the continued fraction representation of the square root of a
-/// number. The elements generated after the first shall constitute the
-/// repeating terms in the continued fraction.
+enum Terms {
+    // Quadratic irrational
+    //     (p + d.sqrt()) / q
+    // where `q` divides `d - p * p`. The initial values of `p` and `q` are
+    // retained to analyse the periodicity of the terms.
+    Quadratic {
+        p: i64,
+        q: i64,
+        d: i64,
+        isqrt_d: i64,
+        p0: i64,
+        q0: i64,
+    },
+    // Rational number
+    //     numerator / denominator
+    // which is exhausted when `denominator` becomes zero.
+    Rational {
+        numerator: i64,
+        denominator: i64,
+        numerator0: i64,
+        denominator0: i64,
+    },
+    Generated(Box<dyn Iterator<Item = i64>>),
+}
+
+/// Generate the continued fraction representation of a number. The terms of
+/// the continued fraction of a quadratic irrational eventually repeat; the
+/// terms of the continued fraction of a rational number are finite in number.
 pub struct ContinuedFraction {
-    num: i64,
-    a0: i64,
-    numerator_addend: i64,
-    denominator: i64,
+    terms: Terms,
+    // Term after which to stop generating terms.
+    last: Option<i64>,
+    exhausted: bool,
 }
 
 impl ContinuedFraction {
+    /// Generate the continued fraction representation of the square root of
+    /// a number. The elements generated after the first shall constitute the
+    /// repeating terms in the continued fraction.
+    ///
+    /// * `num` Must be non-negative.
     pub fn new(num: i64) -> ContinuedFraction {
+        let a0 = utils::isqrt(num);
+        let mut continued_fraction = ContinuedFraction::quadratic(0, num, 1);
+        continued_fraction.last = Some(2 * a0);
+        continued_fraction
+    }
+
+    /// Generate the continued fraction representation of a quadratic
+    /// irrational number
+    ///     (p + d.sqrt()) / q
+    /// endlessly. If `d` is a perfect square, the number is rational, so the
+    /// representation is finite.
+    ///
+    /// * `p`
+    /// * `d` Must be non-negative.
+    /// * `q` Must be non-zero.
+    pub fn quadratic(p: i64, d: i64, q: i64) -> ContinuedFraction {
+        if q == 0 {
+            panic!("denominator of a quadratic irrational cannot be zero");
+        }
+        let isqrt_d = utils::isqrt(d);
+        if isqrt_d * isqrt_d == d {
+            return ContinuedFraction::rational(p + isqrt_d, q);
+        }
+
+        // The recurrence relation requires that `q` divide `d - p * p`. If it
+        // does not, multiply the numerator and denominator by `q.abs()`.
+        let (p, d, q) = if (d - p * p) % q == 0 {
+            (p, d, q)
+        } else {
+            (p * q.abs(), d * q * q, q * q.abs())
+        };
+        ContinuedFraction {
+            terms: Terms::Quadratic {
+                p,
+                q,
+                d,
+                isqrt_d: utils::isqrt(d),
+                p0: p,
+                q0: q,
+            },
+            last: None,
+            exhausted: false,
+        }
+    }
+
+    /// Generate the continued fraction representation of a rational number.
+    ///
+    /// * `numerator`
+    /// * `denominator` Must be non-zero.
+    pub fn rational(numerator: i64, denominator: i64) -> ContinuedFraction {
+        if denominator == 0 {
+            panic!("denominator of a rational number cannot be zero");
+        }
+        // Keep the denominator positive, so that Euclidean division rounds
+        // towards negative infinity.
+        let (numerator, denominator) = if denominator < 0 {
+            (-numerator, -denominator)
+        } else {
+            (numerator, denominator)
+        };
+        ContinuedFraction {
+            terms: Terms::Rational {
+                numerator,
+                denominator,
+                numerator0: numerator,
+                denominator0: denominator,
+            },
+            last: None,
+            exhausted: false,
+        }
+    }
+
+    /// Generate the continued fraction representation of a number whose
+    /// terms are known.
+    ///
+    /// * `terms` Terms of the continued fraction. All terms after the first
+    ///   must be positive.
+    pub fn from_terms(terms: impl Iterator<Item = i64> + 'static) -> ContinuedFraction {
         ContinuedFraction {
-            num,
-            a0: utils::isqrt(num),
-            numerator_addend: 0,
-            denominator: 1,
+            terms: Terms::Generated(Box::new(terms)),
+            last: None,
+            exhausted: false,
+        }
+    }
+
+    /// Generate the continued fraction representation of Euler's number
+    /// endlessly.
+    pub fn e() -> ContinuedFraction {
+        let terms = (1..).flat_map(|k| [1, 2 * k, 1]);
+        ContinuedFraction::from_terms(std::iter::once(2).chain(terms))
+    }
+
+    /// Analyse the periodicity of the continued fraction representation.
+    ///
+    /// Returns the number of terms before the repeating terms begin and the
+    /// number of repeating terms, if these can be determined. The number of
+    /// repeating terms of a rational number is 0.
+    fn periodicity(&self) -> Option<(usize, usize)> {
+        match self.terms {
+            Terms::Quadratic { d, isqrt_d, p0, q0, .. } => {
+                // Each term is determined by the values of `p` and `q`, so the
+                // terms repeat as soon as these do.
+                let mut seen = std::collections::HashMap::new();
+                let (mut p, mut q) = (p0, q0);
+                for idx in 0.. {
+                    if let Some(start) = seen.insert((p, q), idx) {
+                        return Some((start, idx - start));
+                    }
+                    (p, q, _) = ContinuedFraction::quadratic_step(p, q, d, isqrt_d);
+                }
+                unreachable!();
+            }
+            Terms::Rational {
+                numerator0,
+                denominator0,
+                ..
+            } => Some((ContinuedFraction::rational(numerator0, denominator0).count(), 0)),
+            Terms::Generated(_) => None,
         }
     }
+
+    /// Find the number of terms before the repeating terms begin.
+    ///
+    /// Returns `None` if the terms were supplied by the caller. Returns the
+    /// total number of terms for a rational number.
+    pub fn pre_period(&self) -> Option<usize> {
+        Some(self.periodicity()?.0)
+    }
+
+    /// Find the number of repeating terms.
+    ///
+    /// Returns `None` if the terms were supplied by the caller. Returns 0 for
+    /// a rational number.
+    pub fn period(&self) -> Option<usize> {
+        Some(self.periodicity()?.1)
+    }
+
+    /// Generate the convergents of the continued fraction.
+    pub fn convergents<T: utils::ConvergentInteger>(self) -> utils::Convergents<ContinuedFraction, T> {
+        utils::Convergents::new(self)
+    }
+
+    /// Calculate the next term of the continued fraction of a quadratic
+    /// irrational.
+    ///
+    /// * `p`
+    /// * `q`
+    /// * `d`
+    /// * `isqrt_d`
+    ///
+    /// Returns the values of `p` and `q` for the remaining part and the term.
+    fn quadratic_step(p: i64, q: i64, d: i64, isqrt_d: i64) -> (i64, i64, i64) {
+        // The floor of the quadratic irrational can be found from the floor
+        // of the square root, but must be adjusted if `q` is negative.
+        let a = if q > 0 {
+            (p + isqrt_d).div_euclid(q)
+        } else {
+            (-p - isqrt_d - 1).div_euclid(-q)
+        };
+        let p = a * q - p;
+        let q = (d - p * p) / q;
+        (p, q, a)
+    }
 }
 
 impl Iterator for ContinuedFraction {
     type Item = i64;
     fn next(&mut self) -> Option<i64> {
-        // This will happen if the given number was a perfect square. We will
-        // also use this as the condition for detecting repeating terms.
-        if self.denominator == 0 {
+        if self.exhausted {
             return None;
         }
-
-        // If a part of the continued fraction is
-        //     (num.sqrt() + numerator_addend) / denominator
-        // then the next term of the continued fraction, `numerator_addend` and
-        // `denominator` can be found using a recurrence relation.
-        let a = (self.a0 + self.numerator_addend) / self.denominator;
-        self.numerator_addend = a * self.denominator - self.numerator_addend;
-        self.denominator = (self.num - self.numerator_addend.pow(2)) / self.denominator;
+        let a = match &mut self.terms {
+            Terms::Quadratic { p, q, d, isqrt_d, .. } => {
+                let a;
+                (*p, *q, a) = ContinuedFraction::quadratic_step(*p, *q, *d, *isqrt_d);
+                a
+            }
+            Terms::Rational {
+                numerator, denominator, ..
+            } => {
+                if *denominator == 0 {
+                    return None;
+                }
+                let a = numerator.div_euclid(*denominator);
+                (*numerator, *denominator) = (*denominator, numerator.rem_euclid(*denominator));
+                a
+            }
+            Terms::Generated(terms) => terms.next()?,
+        };
 
         // When this happens, the terms will start repeating.
-        if a == self.a0 * 2 {
-            self.denominator = 0;
+        if self.last == Some(a) {
+            self.exhausted = true;
         }
         Some(a)
     }
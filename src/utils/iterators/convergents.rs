@@ -0,0 +1,88 @@
+use crate::utils;
+
+/// Integer type in which the numerators and denominators of convergents can
+/// be calculated.
+pub trait ConvergentInteger: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    /// Calculate `self * a + other`.
+    ///
+    /// * `a` Term of a continued fraction.
+    /// * `other`
+    fn mul_add(&self, a: i64, other: &Self) -> Self;
+}
+
+impl ConvergentInteger for i128 {
+    fn zero() -> i128 {
+        0
+    }
+    fn one() -> i128 {
+        1
+    }
+    fn mul_add(&self, a: i64, other: &i128) -> i128 {
+        self * a as i128 + other
+    }
+}
+
+impl ConvergentInteger for utils::Long {
+    fn zero() -> utils::Long {
+        utils::Long::zero()
+    }
+    fn one() -> utils::Long {
+        utils::Long::one()
+    }
+    /// Panics if `a` is negative, because `Long` is unsigned.
+    fn mul_add(&self, a: i64, other: &utils::Long) -> utils::Long {
+        let a = u64::try_from(a).expect("terms must be non-negative");
+        &(self * &utils::Long::from(a)) + other
+    }
+}
+
+impl ConvergentInteger for utils::BigInt {
+    fn zero() -> utils::BigInt {
+        utils::BigInt::default()
+    }
+    fn one() -> utils::BigInt {
+        utils::BigInt::from(1)
+    }
+    fn mul_add(&self, a: i64, other: &utils::BigInt) -> utils::BigInt {
+        &(self * &utils::BigInt::from(a)) + other
+    }
+}
+
+/// Generate the convergents of a continued fraction. The numerator and
+/// denominator of each convergent are obtained from those of the previous two
+/// using a recurrence relation.
+pub struct Convergents<I, T> {
+    terms: I,
+    // Numerators and denominators of the previous two convergents.
+    prev: (T, T),
+    curr: (T, T),
+}
+
+impl<I: Iterator<Item = i64>, T: ConvergentInteger> Convergents<I, T> {
+    /// Generate the convergents of a continued fraction.
+    ///
+    /// * `terms` Terms of the continued fraction.
+    pub fn new(terms: I) -> Convergents<I, T> {
+        Convergents {
+            terms,
+            prev: (T::zero(), T::one()),
+            curr: (T::one(), T::zero()),
+        }
+    }
+}
+
+impl<I: Iterator<Item = i64>, T: ConvergentInteger> Iterator for Convergents<I, T> {
+    type Item = (T, T);
+    fn next(&mut self) -> Option<(T, T)> {
+        let a = self.terms.next()?;
+        let next = (
+            self.curr.0.mul_add(a, &self.prev.0),
+            self.curr.1.mul_add(a, &self.prev.1),
+        );
+        self.prev = std::mem::replace(&mut self.curr, next);
+        Some(self.curr.clone())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::utils;
+
+/// Generate the Farey sequence of some order: the reduced fractions between 0
+/// and 1 (both inclusive) whose denominators do not exceed the order, in
+/// ascending order. Each term is obtained from the previous two.
+pub struct Farey {
+    order: i64,
+    prev: (i64, i64),
+    curr: (i64, i64),
+    // Upper bound of the terms, and whether it is included.
+    end: utils::Rational,
+    inclusive: bool,
+}
+
+impl Farey {
+    /// Generate the Farey sequence of some order.
+    ///
+    /// * `order` Must be positive.
+    pub fn new(order: i64) -> Farey {
+        if order <= 0 {
+            panic!("order of a Farey sequence must be positive");
+        }
+        // Pretend that -1/1 precedes 0/1, so that the first term generated is
+        // 0/1.
+        Farey {
+            order,
+            prev: (-1, 1),
+            curr: (0, 1),
+            end: utils::Rational::from(1),
+            inclusive: true,
+        }
+    }
+
+    /// Generate the terms of the Farey sequence of some order which lie
+    /// strictly between two rational numbers.
+    ///
+    /// * `order` Must be positive.
+    /// * `start` Lower bound.
+    /// * `end` Upper bound.
+    pub fn between(order: i64, start: utils::Rational, end: utils::Rational) -> Farey {
+        if order <= 0 {
+            panic!("order of a Farey sequence must be positive");
+        }
+        // The recurrence relation requires two consecutive terms. The first
+        // term generated is the one following the lower bound. If the lower
+        // bound is not a term, use the term preceding it.
+        let (left, right) = start.farey_neighbours(order);
+        let prev = if start.denominator() <= order { start } else { left };
+        Farey {
+            order,
+            prev: (prev.numerator(), prev.denominator()),
+            curr: (right.numerator(), right.denominator()),
+            end,
+            inclusive: false,
+        }
+    }
+
+    /// Count the reduced fractions strictly between two rational numbers
+    /// whose denominators do not exceed some limit, without generating them.
+    /// Counting all fractions (reduced or not) is easy; the count of reduced
+    /// fractions is obtained from these counts using Möbius inversion.
+    ///
+    /// * `order` Must be positive.
+    /// * `start` Lower bound.
+    /// * `end` Upper bound.
+    pub fn count(order: i64, start: utils::Rational, end: utils::Rational) -> i64 {
+        if order <= 0 {
+            panic!("order of a Farey sequence must be positive");
+        }
+        if start >= end {
+            return 0;
+        }
+        let order = order as usize;
+        let (a, b) = (start.numerator() as i128, start.denominator() as i128);
+        let (c, d) = (end.numerator() as i128, end.denominator() as i128);
+
+        // Cumulative counts of all fractions with each denominator.
+        let mut counts = vec![0; order + 1];
+        for denominator in 1..=order {
+            let q = denominator as i128;
+            let count = (c * q - 1).div_euclid(d) - (a * q).div_euclid(b);
+            counts[denominator] = counts[denominator - 1] + count as i64;
+        }
+
+        // Möbius function of every number up to the order.
+        let mut mobius = vec![1i64; order + 1];
+        let mut composite = vec![false; order + 1];
+        for num in 2..=order {
+            if composite[num] {
+                continue;
+            }
+            for multiple in (num..=order).step_by(num) {
+                composite[multiple] = multiple != num;
+                mobius[multiple] = -mobius[multiple];
+            }
+            for multiple in (num * num..=order).step_by(num * num) {
+                mobius[multiple] = 0;
+            }
+        }
+
+        (1..=order)
+            .filter(|&num| mobius[num] != 0)
+            .map(|num| mobius[num] * counts[order / num])
+            .sum()
+    }
+}
+
+impl Iterator for Farey {
+    type Item = utils::Rational;
+    fn next(&mut self) -> Option<utils::Rational> {
+        let (a, b) = self.prev;
+        let (c, d) = self.curr;
+        let current = utils::Rational::new(c, d);
+        if current > self.end || current == self.end && !self.inclusive {
+            return None;
+        }
+        let k = (self.order + b) / d;
+        (self.prev, self.curr) = ((c, d), (k * c - a, k * d - b));
+        Some(current)
+    }
+}
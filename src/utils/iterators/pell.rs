@@ -0,0 +1,185 @@
+use crate::utils;
+use crate::utils::ConvergentInteger;
+
+/// Generate the non-negative solutions of the generalised Pell equation
+///     x * x - d * y * y = n
+/// in ascending order. The first solution generated is the smallest one. If
+/// `n` is 1, this is the trivial solution (1, 0); the fundamental solution is
+/// the second one.
+pub struct Pell {
+    d: utils::BigInt,
+    fundamental: (utils::BigInt, utils::BigInt),
+    pending: std::collections::BinaryHeap<std::cmp::Reverse<(utils::BigInt, utils::BigInt)>>,
+}
+
+impl Pell {
+    /// Generate the non-negative solutions of a generalised Pell equation.
+    ///
+    /// * `d` Must be positive and not a perfect square.
+    /// * `n` Must be non-zero.
+    pub fn new(d: i64, n: i64) -> Pell {
+        if n == 0 {
+            panic!("constant term of the generalised Pell equation cannot be zero");
+        }
+        let (t, u) = Pell::fundamental(d);
+        let fundamental = (utils::BigInt::from(t), utils::BigInt::from(u));
+        let negative = Pell::fundamental_negative(d).map(|(t, u)| (utils::BigInt::from(t), utils::BigInt::from(u)));
+        let d_big = utils::BigInt::from(d);
+
+        // Every solution is obtained from the smallest non-negative solution
+        // of its class by repeated multiplication with the fundamental
+        // solution of the Pell equation. Find one solution of each class,
+        // then move it to the smallest non-negative solution of its class.
+        let mut smallest = std::collections::BTreeSet::new();
+        for (x, y) in Pell::lmm(d, n, &negative) {
+            let (x, y) = (x.abs(), y.abs());
+            for (x, y) in [(x.clone(), y.clone()), (-&x, y.clone()), (x, -&y)] {
+                let positive = match (x.is_negative(), y.is_negative()) {
+                    (false, false) => true,
+                    (false, true) => n > 0,
+                    (true, false) => n < 0,
+                    (true, true) => false,
+                };
+                if !positive {
+                    continue;
+                }
+                let mut solution = (x, y);
+                while solution.0.is_negative() || solution.1.is_negative() {
+                    solution = Pell::multiply(&solution, &fundamental, &d_big);
+                }
+                loop {
+                    let previous = Pell::multiply(&solution, &(fundamental.0.clone(), -&fundamental.1), &d_big);
+                    if previous.0.is_negative() || previous.1.is_negative() {
+                        break;
+                    }
+                    solution = previous;
+                }
+                smallest.insert(solution);
+            }
+        }
+        Pell {
+            d: d_big,
+            fundamental,
+            pending: smallest.into_iter().map(std::cmp::Reverse).collect(),
+        }
+    }
+
+    /// Find the fundamental solution of the Pell equation
+    ///     x * x - d * y * y = 1
+    /// using the continued fraction representation of the square root of
+    /// `d`.
+    ///
+    /// * `d` Must be positive and not a perfect square.
+    ///
+    /// Returns the smallest positive solution.
+    pub fn fundamental(d: i64) -> (utils::Long, utils::Long) {
+        let continued_fraction = Pell::continued_fraction(d);
+        let r = continued_fraction.period().unwrap();
+        let solution_idx = if r.is_multiple_of(2) { r - 1 } else { 2 * r - 1 };
+        continued_fraction.convergents().nth(solution_idx).unwrap()
+    }
+
+    /// Find the fundamental solution of the negative Pell equation
+    ///     x * x - d * y * y = -1
+    /// using the continued fraction representation of the square root of
+    /// `d`.
+    ///
+    /// * `d` Must be positive and not a perfect square.
+    ///
+    /// Returns the smallest positive solution, if the equation is solvable.
+    pub fn fundamental_negative(d: i64) -> Option<(utils::Long, utils::Long)> {
+        let continued_fraction = Pell::continued_fraction(d);
+        let r = continued_fraction.period().unwrap();
+        if r.is_multiple_of(2) {
+            return None;
+        }
+        continued_fraction.convergents().nth(r - 1)
+    }
+
+    /// Generate the continued fraction representation of the square root of
+    /// a number.
+    ///
+    /// * `d` Must be positive and not a perfect square.
+    fn continued_fraction(d: i64) -> utils::ContinuedFraction {
+        let isqrt_d = utils::isqrt(d);
+        if d <= 0 || isqrt_d * isqrt_d == d {
+            panic!("coefficient of the Pell equation must be positive and not a perfect square");
+        }
+        utils::ContinuedFraction::quadratic(0, d, 1)
+    }
+
+    /// Multiply two numbers of the form `x + y * d.sqrt()`.
+    ///
+    /// * `a`
+    /// * `b`
+    /// * `d`
+    fn multiply(
+        a: &(utils::BigInt, utils::BigInt),
+        b: &(utils::BigInt, utils::BigInt),
+        d: &utils::BigInt,
+    ) -> (utils::BigInt, utils::BigInt) {
+        (&(&a.0 * &b.0) + &(&(&a.1 * &b.1) * d), &(&a.0 * &b.1) + &(&a.1 * &b.0))
+    }
+
+    /// Find at least one solution of each class of solutions of a
+    /// generalised Pell equation using the Lagrange-Matthews-Mollin
+    /// algorithm.
+    ///
+    /// * `d` Must be positive and not a perfect square.
+    /// * `n` Must be non-zero.
+    /// * `negative` Fundamental solution of the negative Pell equation.
+    fn lmm(d: i64, n: i64, negative: &Option<(utils::BigInt, utils::BigInt)>) -> Vec<(utils::BigInt, utils::BigInt)> {
+        let d_big = utils::BigInt::from(d);
+        let mut solutions = vec![];
+        for f in (1..).take_while(|f| f * f <= n.abs()).filter(|f| n % (f * f) == 0) {
+            let m = n / (f * f);
+            let m_abs = m.abs();
+            let f = utils::BigInt::from(f);
+            for z in (-(m_abs - 1) / 2..=m_abs / 2).filter(|z| (z * z - d).rem_euclid(m_abs) == 0) {
+                // Expand
+                //     (z + d.sqrt()) / m.abs()
+                // and look for a remainder whose denominator is 1 or -1. The
+                // preceding convergent then yields a solution.
+                let mut states = std::collections::HashSet::new();
+                let mut state = (z, m_abs);
+                let mut terms = utils::ContinuedFraction::quadratic(z, d, m_abs);
+                let mut convergents = (
+                    (utils::BigInt::from(0), utils::BigInt::from(1)),
+                    (utils::BigInt::from(1), utils::BigInt::from(0)),
+                );
+                while states.insert(state) {
+                    let (p, q) = state;
+                    let term = terms.next().unwrap();
+                    state = (term * q - p, (d - (term * q - p).pow(2)) / q);
+                    let (prev, curr) = convergents;
+                    let next = (curr.0.mul_add(term, &prev.0), curr.1.mul_add(term, &prev.1));
+                    convergents = (curr, next);
+                    if state.1.abs() != 1 {
+                        continue;
+                    }
+                    let (a, b) = &convergents.1;
+                    let g = &(a * &utils::BigInt::from(m_abs)) - &(b * &utils::BigInt::from(z));
+                    let value = &(&g * &g) - &(&(b * b) * &d_big);
+                    if value == utils::BigInt::from(m) {
+                        solutions.push((&f * &g, &f * b));
+                    } else if let (true, Some(negative)) = (value == utils::BigInt::from(-m), negative) {
+                        let (x, y) = Pell::multiply(&(g, b.clone()), negative, &d_big);
+                        solutions.push((&f * &x, &f * &y));
+                    }
+                    break;
+                }
+            }
+        }
+        solutions
+    }
+}
+
+impl Iterator for Pell {
+    type Item = (utils::Long, utils::Long);
+    fn next(&mut self) -> Option<(utils::Long, utils::Long)> {
+        let std::cmp::Reverse(solution) = self.pending.pop()?;
+        self.pending
+            .push(std::cmp::Reverse(Pell::multiply(&solution, &self.fundamental, &self.d)));
+        Some((solution.0.magnitude().clone(), solution.1.magnitude().clone()))
+    }
+}
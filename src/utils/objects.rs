@@ -1,5 +1,10 @@
+pub mod big_int;
+pub mod decimal_expansion;
 pub mod fraction;
+pub mod linear_sieve;
 pub mod long;
 pub mod pandigital_checker;
 pub mod poker_hand;
+pub mod rational;
 pub mod sieve_of_atkin;
+pub mod stern_brocot;
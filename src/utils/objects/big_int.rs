@@ -0,0 +1,240 @@
+use crate::utils;
+
+/// Arbitrary-precision signed integer type which stores the sign and the
+/// magnitude of a number separately. Zero is never negative.
+#[derive(Clone, Default, Eq, Hash, PartialEq)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: utils::Long,
+}
+
+impl BigInt {
+    /// Construct an arbitrary-precision signed integer.
+    ///
+    /// * `s` Big-endian string of decimal digits, optionally preceded by a
+    ///   sign. Panics if it cannot be parsed. Use `str::parse` to handle
+    ///   errors instead.
+    pub fn new(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    /// Construct an arbitrary-precision signed integer.
+    ///
+    /// * `negative` Whether the number is negative. Ignored if the magnitude
+    ///   is zero.
+    /// * `magnitude` Absolute value.
+    pub fn from_parts(negative: bool, magnitude: utils::Long) -> BigInt {
+        BigInt {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    /// Obtain the absolute value of this number as an unsigned
+    /// arbitrary-precision integer.
+    pub fn magnitude(&self) -> &utils::Long {
+        &self.magnitude
+    }
+
+    /// Check whether this number is negative.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Check whether this number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    /// Calculate the absolute value of this number.
+    pub fn abs(&self) -> BigInt {
+        BigInt {
+            negative: false,
+            magnitude: self.magnitude.clone(),
+        }
+    }
+
+    /// Determine the sign of this number.
+    ///
+    /// Returns -1, 0 or 1 if this number is negative, zero or positive
+    /// respectively.
+    pub fn signum(&self) -> i32 {
+        if self.negative {
+            -1
+        } else if self.is_zero() {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Add a signed number to this number in place.
+    ///
+    /// * `negative` Sign of the addend.
+    /// * `magnitude` Absolute value of the addend.
+    fn add_signed(&mut self, negative: bool, magnitude: &utils::Long) {
+        if self.negative == negative {
+            self.magnitude += magnitude;
+            return;
+        }
+
+        // The signs differ, so the result takes the sign of the operand with
+        // the larger magnitude.
+        *self = match self.magnitude.checked_sub(magnitude) {
+            Some(difference) => BigInt::from_parts(self.negative, difference),
+            None => BigInt::from_parts(negative, magnitude - &self.magnitude),
+        };
+    }
+
+    /// Divide this number by another number, rounding the quotient towards
+    /// zero. This matches the behaviour of the primitive integer types: the
+    /// remainder has the same sign as the dividend.
+    ///
+    /// * `other` Divisor. Must be non-zero.
+    ///
+    /// Returns the quotient and remainder.
+    pub fn div_rem(&self, other: &BigInt) -> (BigInt, BigInt) {
+        let (quotient, rem) = self.magnitude.div_rem(&other.magnitude);
+        (
+            BigInt::from_parts(self.negative != other.negative, quotient),
+            BigInt::from_parts(self.negative, rem),
+        )
+    }
+}
+
+impl std::str::FromStr for BigInt {
+    type Err = utils::ParseLongError;
+    fn from_str(s: &str) -> Result<BigInt, utils::ParseLongError> {
+        match s.strip_prefix('-') {
+            Some(s) if s.starts_with(['+', '-']) => Err(utils::ParseLongError::InvalidDigit),
+            Some(s) => Ok(BigInt::from_parts(true, s.parse()?)),
+            None => Ok(BigInt::from_parts(false, s.parse()?)),
+        }
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(num: i64) -> BigInt {
+        BigInt::from_parts(num < 0, utils::Long::from(num.unsigned_abs()))
+    }
+}
+
+impl From<utils::Long> for BigInt {
+    fn from(magnitude: utils::Long) -> BigInt {
+        BigInt::from_parts(false, magnitude)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> std::cmp::Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (negative, _) => other.negative.cmp(&negative),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::ops::Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+impl std::ops::Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl std::ops::AddAssign<&BigInt> for BigInt {
+    fn add_assign(&mut self, other: &BigInt) {
+        self.add_signed(other.negative, &other.magnitude);
+    }
+}
+
+impl std::ops::Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn add(self, other: &BigInt) -> BigInt {
+        let mut result = self.clone();
+        result += other;
+        result
+    }
+}
+
+impl std::ops::SubAssign<&BigInt> for BigInt {
+    fn sub_assign(&mut self, other: &BigInt) {
+        self.add_signed(!other.negative, &other.magnitude);
+    }
+}
+
+impl std::ops::Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn sub(self, other: &BigInt) -> BigInt {
+        let mut result = self.clone();
+        result -= other;
+        result
+    }
+}
+
+impl std::ops::MulAssign<&BigInt> for BigInt {
+    fn mul_assign(&mut self, other: &BigInt) {
+        *self = &*self * other;
+    }
+}
+
+impl std::ops::Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != other.negative, &self.magnitude * &other.magnitude)
+    }
+}
+
+impl std::ops::DivAssign<&BigInt> for BigInt {
+    fn div_assign(&mut self, other: &BigInt) {
+        *self = self.div_rem(other).0;
+    }
+}
+
+impl std::ops::Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl std::ops::RemAssign<&BigInt> for BigInt {
+    fn rem_assign(&mut self, other: &BigInt) {
+        *self = self.div_rem(other).1;
+    }
+}
+
+impl std::ops::Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+impl std::fmt::Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.magnitude)
+    }
+}
+
+impl std::fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "negative = {}, {:?}", self.negative, self.magnitude)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::utils;
+
+/// Positional expansion of a rational number in some base. The expansion
+/// consists of an integer part, a non-repeating prefix and a repeating block
+/// (which is empty if the expansion terminates). The digits after the radix
+/// point are not stored; they are generated on demand, because the repeating
+/// block can be arbitrarily long.
+#[derive(Clone)]
+pub struct DecimalExpansion {
+    radix: u32,
+    negative: bool,
+    integer_part: utils::Long,
+    // The fractional part is the ratio of these two numbers, and is less
+    // than 1.
+    remainder: utils::Long,
+    denominator: utils::Long,
+    prefix_length: usize,
+    period: utils::Long,
+}
+
+impl DecimalExpansion {
+    /// Construct the positional expansion of a rational number.
+    ///
+    /// * `numerator`
+    /// * `denominator` Must be non-zero.
+    /// * `radix` Must be between 2 and 36 (both inclusive).
+    pub fn new(numerator: i64, denominator: i64, radix: u32) -> DecimalExpansion {
+        let fraction = utils::Fraction::new(utils::BigInt::from(numerator), utils::BigInt::from(denominator));
+        DecimalExpansion::from_fraction(&fraction, radix)
+    }
+
+    /// Construct the positional expansion of a rational number.
+    ///
+    /// * `fraction`
+    /// * `radix` Must be between 2 and 36 (both inclusive).
+    pub fn from_fraction(fraction: &utils::Fraction, radix: u32) -> DecimalExpansion {
+        if !(2..=36).contains(&radix) {
+            panic!("radix must be between 2 and 36");
+        }
+        let mut fraction = fraction.clone();
+        fraction.reduce();
+        let denominator = fraction.denominator().clone();
+        let (integer_part, remainder) = fraction.numerator().magnitude().div_rem(&denominator);
+
+        // Split the denominator into a part having only the prime factors of
+        // the radix and a part coprime to the radix. The former determines
+        // the length of the non-repeating prefix; the latter, the period.
+        let radix_long = utils::Long::from(radix);
+        let mut coprime_part = denominator.clone();
+        loop {
+            let gcd = coprime_part.gcd(&radix_long);
+            if gcd == utils::Long::one() {
+                break;
+            }
+            coprime_part /= &gcd;
+        }
+        let mut radix_part = &denominator / &coprime_part;
+        let mut prefix_length = 0;
+        while radix_part != utils::Long::one() {
+            radix_part /= &radix_part.gcd(&radix_long);
+            prefix_length += 1;
+        }
+        let period = if coprime_part == utils::Long::one() {
+            utils::Long::zero()
+        } else {
+            DecimalExpansion::multiplicative_order(&radix_long, &coprime_part)
+        };
+
+        DecimalExpansion {
+            radix,
+            negative: fraction.numerator().is_negative(),
+            integer_part,
+            remainder,
+            denominator,
+            prefix_length,
+            period,
+        }
+    }
+
+    /// Calculate the multiplicative order of a number modulo another number.
+    ///
+    /// * `num`
+    /// * `modulus` Must be greater than 1 and coprime to `num`.
+    ///
+    /// Returns the smallest positive exponent to which `num` must be raised
+    /// to obtain 1 modulo `modulus`.
+    fn multiplicative_order(num: &utils::Long, modulus: &utils::Long) -> utils::Long {
+        let one = utils::Long::one();
+        let mut result = one.clone();
+        for (prime, exp) in modulus.factorise() {
+            // Find the order modulo the prime by removing factors from the
+            // order of the multiplicative group.
+            let mut order = &prime - 1;
+            for (factor, factor_exp) in order.factorise() {
+                for _ in 0..factor_exp {
+                    let reduced = &order / &factor;
+                    if num.pow_mod(&reduced, &prime) != one {
+                        break;
+                    }
+                    order = reduced;
+                }
+            }
+
+            // Lift it to the prime power. At each step, the order either
+            // stays the same or gets multiplied by the prime.
+            let mut prime_power = prime.clone();
+            for _ in 1..exp {
+                prime_power = &prime_power * &prime;
+                if num.pow_mod(&order, &prime_power) != one {
+                    order = &order * &prime;
+                }
+            }
+            result = result.lcm(&order);
+        }
+        result
+    }
+
+    /// Obtain the base of the expansion.
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// Check whether the expanded number is negative. The expansion itself is
+    /// that of its absolute value.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Obtain the integer part of the absolute value of the expanded number.
+    pub fn integer_part(&self) -> &utils::Long {
+        &self.integer_part
+    }
+
+    /// Obtain the number of digits after the radix point which do not repeat.
+    pub fn prefix_length(&self) -> usize {
+        self.prefix_length
+    }
+
+    /// Obtain the number of digits in the repeating block. This is zero if the
+    /// expansion terminates.
+    pub fn period(&self) -> &utils::Long {
+        &self.period
+    }
+
+    /// Obtain the digits after the radix point, most significant first. If
+    /// the expansion terminates, the iterator is finite; otherwise, it is
+    /// infinite.
+    pub fn fractional_digits(&self) -> impl Iterator<Item = u32> + '_ {
+        let mut remainder = self.remainder.clone();
+        std::iter::from_fn(move || {
+            if remainder.is_zero() {
+                return None;
+            }
+            let (digit, rem) = (&remainder * self.radix).div_rem(&self.denominator);
+            remainder = rem;
+            Some(u64::try_from(&digit).unwrap() as u32)
+        })
+    }
+
+    /// Obtain the digits after the radix point which do not repeat.
+    pub fn prefix(&self) -> Vec<u32> {
+        self.fractional_digits().take(self.prefix_length).collect()
+    }
+
+    /// Obtain the digits in the repeating block. This is empty if the
+    /// expansion terminates. The block may be too long to fit in memory;
+    /// check `period` first.
+    pub fn repeating(&self) -> Vec<u32> {
+        let period = u64::try_from(&self.period).expect("period too long") as usize;
+        self.fractional_digits().skip(self.prefix_length).take(period).collect()
+    }
+}
+
+impl std::fmt::Display for DecimalExpansion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.integer_part.to_str_radix(self.radix))?;
+        let to_char = |digit| std::char::from_digit(digit, self.radix).unwrap();
+        let (prefix, repeating) = (self.prefix(), self.repeating());
+        if !prefix.is_empty() || !repeating.is_empty() {
+            write!(f, ".{}", prefix.into_iter().map(to_char).collect::<String>())?;
+        }
+        if !repeating.is_empty() {
+            write!(f, "({})", repeating.into_iter().map(to_char).collect::<String>())?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
 use crate::utils;
 
-/// Rational numbers stored as a pair of `Long`s.
+/// Rational numbers stored as a signed numerator and a positive denominator.
+/// Arithmetic operations do not reduce their results to the lowest form; call
+/// `reduce` to do that. Comparisons are by value, so they are unaffected by
+/// this.
 #[derive(Clone)]
 pub struct Fraction {
-    numerator: utils::Long,
+    numerator: utils::BigInt,
     denominator: utils::Long,
 }
 
@@ -11,52 +14,421 @@ impl Fraction {
     /// Construct a rational number.
     ///
     /// * `numerator`
-    /// * `denominator`
+    /// * `denominator` Must be non-zero.
     ///
     /// Returns a rational number which is the ratio of the given numbers. It
     /// is not reduced to its lowest form.
-    pub fn from(numerator: u32, denominator: u32) -> Fraction {
+    pub fn new(numerator: utils::BigInt, denominator: utils::BigInt) -> Fraction {
+        if denominator.is_zero() {
+            panic!("denominator of a rational number cannot be zero");
+        }
+        let negative = numerator.is_negative() != denominator.is_negative();
         Fraction {
-            numerator: utils::Long::from(numerator),
-            denominator: utils::Long::from(denominator),
+            numerator: utils::BigInt::from_parts(negative, numerator.magnitude().clone()),
+            denominator: denominator.magnitude().clone(),
+        }
+    }
+
+    /// Construct a rational number.
+    ///
+    /// * `numerator`
+    /// * `denominator` Must be non-zero.
+    ///
+    /// Returns a rational number which is the ratio of the given numbers. It
+    /// is not reduced to its lowest form.
+    pub fn from(numerator: u32, denominator: u32) -> Fraction {
+        Fraction::new(
+            utils::BigInt::from(numerator as i64),
+            utils::BigInt::from(denominator as i64),
+        )
+    }
+
+    /// Obtain the numerator. Its sign is the sign of the rational number.
+    pub fn numerator(&self) -> &utils::BigInt {
+        &self.numerator
+    }
+
+    /// Obtain the denominator. It is always positive.
+    pub fn denominator(&self) -> &utils::Long {
+        &self.denominator
+    }
+
+    /// Reduce the rational number to its lowest form.
+    pub fn reduce(&mut self) {
+        let gcd = self.numerator.magnitude().gcd(&self.denominator);
+        if gcd != utils::Long::one() {
+            self.numerator =
+                utils::BigInt::from_parts(self.numerator.is_negative(), self.numerator.magnitude() / &gcd);
+            self.denominator = &self.denominator / &gcd;
         }
     }
 
     /// Convert the rational number into its reciprocal.
     pub fn invert(&mut self) {
-        std::mem::swap(&mut self.numerator, &mut self.denominator);
+        if self.numerator.is_zero() {
+            panic!("reciprocal of zero is undefined");
+        }
+        let negative = self.numerator.is_negative();
+        let magnitude = std::mem::take(&mut self.denominator);
+        self.denominator = self.numerator.magnitude().clone();
+        self.numerator = utils::BigInt::from_parts(negative, magnitude);
     }
 
     /// Obtain the number of decimal digits of the numerator and denominator.
     /// Mirrors the method of the same name of `Long`.
     pub fn len(&self) -> (usize, usize) {
-        (self.numerator.len(), self.denominator.len())
+        (self.numerator.magnitude().len(), self.denominator.len())
     }
 
     /// Calculate the sum of all decimal digits of the numerator and
     /// denominator. Mirrors the method of the same name of `Long`.
     pub fn sum(&self) -> (i64, i64) {
-        (self.numerator.sum(), self.denominator.sum())
+        (self.numerator.magnitude().sum(), self.denominator.sum())
+    }
+
+    /// Calculate the largest integer less than or equal to this rational
+    /// number.
+    pub fn floor(&self) -> utils::BigInt {
+        let (quotient, rem) = self.numerator.div_rem(&utils::BigInt::from(self.denominator.clone()));
+        if rem.is_negative() {
+            &quotient - &utils::BigInt::from(1)
+        } else {
+            quotient
+        }
+    }
+
+    /// Find the neighbours of this rational number among the rational
+    /// numbers whose denominators do not exceed a limit.
+    ///
+    /// * `max_denominator` Must be positive.
+    ///
+    /// Returns the largest such rational number less than this one and the
+    /// smallest such rational number greater than it.
+    pub fn farey_neighbours(&self, max_denominator: &utils::Long) -> (Fraction, Fraction) {
+        // Shifting by an integer does not change any denominator, so the
+        // search can be carried out between 1 and 2, where the Stern–Brocot
+        // tree applies.
+        let shift: Fraction = (&self.floor() - &utils::BigInt::from(1)).into();
+        let (left, right) = utils::SternBrocot::neighbours(&(self - &shift), max_denominator);
+        (&left + &shift, &right + &shift)
+    }
+
+    /// Find the rational number closest to this one among the rational
+    /// numbers whose denominators do not exceed a limit. Ties are broken in
+    /// favour of the smaller denominator.
+    ///
+    /// * `max_denominator` Must be positive.
+    ///
+    /// Returns the best approximation, reduced to its lowest form.
+    pub fn best_approximation(&self, max_denominator: &utils::Long) -> Fraction {
+        let mut fraction = self.clone();
+        fraction.reduce();
+        if fraction.denominator <= *max_denominator {
+            return fraction;
+        }
+        let (left, right) = fraction.farey_neighbours(max_denominator);
+        match (&fraction - &left).cmp(&(&right - &fraction)) {
+            std::cmp::Ordering::Less => left,
+            std::cmp::Ordering::Greater => right,
+            std::cmp::Ordering::Equal if left.denominator <= right.denominator => left,
+            std::cmp::Ordering::Equal => right,
+        }
+    }
+
+    /// Find the rational number closest to a real number among the rational
+    /// numbers whose denominators do not exceed a limit.
+    ///
+    /// * `num` Must be finite.
+    /// * `max_denominator` Must be positive.
+    ///
+    /// Returns the best approximation, reduced to its lowest form.
+    pub fn approximate(num: f64, max_denominator: &utils::Long) -> Fraction {
+        if !num.is_finite() {
+            panic!("only finite numbers can be approximated");
+        }
+        // Every finite floating-point number is a rational number whose
+        // denominator is a power of 2.
+        let bits = num.to_bits();
+        let exponent = ((bits >> 52) & 0x7FF) as i32;
+        let mantissa = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = if exponent == 0 {
+            (mantissa, -1074)
+        } else {
+            (mantissa | 1 << 52, exponent - 1075)
+        };
+        let mantissa = utils::BigInt::from_parts(num < 0.0, utils::Long::from(mantissa));
+        let fraction = if exponent >= 0 {
+            Fraction::new(
+                &mantissa * &utils::BigInt::from(&utils::Long::one() << exponent as u32),
+                utils::BigInt::from(1),
+            )
+        } else {
+            Fraction::new(mantissa, utils::BigInt::from(&utils::Long::one() << -exponent as u32))
+        };
+        fraction.best_approximation(max_denominator)
+    }
+
+    /// Multiply an integer by the denominator.
+    ///
+    /// * `other`
+    fn scale_integer(&self, other: &utils::BigInt) -> utils::BigInt {
+        utils::BigInt::from_parts(other.is_negative(), other.magnitude() * &self.denominator)
+    }
+
+    /// Multiply the numerator by a number.
+    ///
+    /// * `other`
+    fn scale_numerator(&self, other: &utils::Long) -> utils::BigInt {
+        utils::BigInt::from_parts(self.numerator.is_negative(), self.numerator.magnitude() * other)
     }
 }
 
-impl std::ops::AddAssign<u32> for Fraction {
-    fn add_assign(&mut self, other: u32) {
-        self.numerator += &(&self.denominator * other);
+impl From<utils::BigInt> for Fraction {
+    fn from(numerator: utils::BigInt) -> Fraction {
+        Fraction {
+            numerator,
+            denominator: utils::Long::one(),
+        }
+    }
+}
+
+impl std::ops::Neg for &Fraction {
+    type Output = Fraction;
+    fn neg(self) -> Fraction {
+        Fraction {
+            numerator: -&self.numerator,
+            denominator: self.denominator.clone(),
+        }
+    }
+}
+
+impl std::ops::AddAssign<&utils::BigInt> for Fraction {
+    fn add_assign(&mut self, other: &utils::BigInt) {
+        self.numerator += &self.scale_integer(other);
     }
 }
 
-impl std::ops::Add<u32> for &Fraction {
+impl std::ops::Add<&utils::BigInt> for &Fraction {
     type Output = Fraction;
-    fn add(self, other: u32) -> Fraction {
+    fn add(self, other: &utils::BigInt) -> Fraction {
         let mut result = self.clone();
         result += other;
         result
     }
 }
 
+impl std::ops::SubAssign<&utils::BigInt> for Fraction {
+    fn sub_assign(&mut self, other: &utils::BigInt) {
+        self.numerator -= &self.scale_integer(other);
+    }
+}
+
+impl std::ops::Sub<&utils::BigInt> for &Fraction {
+    type Output = Fraction;
+    fn sub(self, other: &utils::BigInt) -> Fraction {
+        let mut result = self.clone();
+        result -= other;
+        result
+    }
+}
+
+impl std::ops::MulAssign<&utils::BigInt> for Fraction {
+    fn mul_assign(&mut self, other: &utils::BigInt) {
+        self.numerator *= other;
+    }
+}
+
+impl std::ops::Mul<&utils::BigInt> for &Fraction {
+    type Output = Fraction;
+    fn mul(self, other: &utils::BigInt) -> Fraction {
+        let mut result = self.clone();
+        result *= other;
+        result
+    }
+}
+
+impl std::ops::DivAssign<&utils::BigInt> for Fraction {
+    fn div_assign(&mut self, other: &utils::BigInt) {
+        if other.is_zero() {
+            panic!("division by zero");
+        }
+        self.numerator = utils::BigInt::from_parts(
+            self.numerator.is_negative() != other.is_negative(),
+            self.numerator.magnitude().clone(),
+        );
+        self.denominator = &self.denominator * other.magnitude();
+    }
+}
+
+impl std::ops::Div<&utils::BigInt> for &Fraction {
+    type Output = Fraction;
+    fn div(self, other: &utils::BigInt) -> Fraction {
+        let mut result = self.clone();
+        result /= other;
+        result
+    }
+}
+
+impl std::ops::AddAssign<i64> for Fraction {
+    fn add_assign(&mut self, other: i64) {
+        *self += &utils::BigInt::from(other);
+    }
+}
+
+impl std::ops::Add<i64> for &Fraction {
+    type Output = Fraction;
+    fn add(self, other: i64) -> Fraction {
+        let mut result = self.clone();
+        result += other;
+        result
+    }
+}
+
+impl std::ops::SubAssign<i64> for Fraction {
+    fn sub_assign(&mut self, other: i64) {
+        *self -= &utils::BigInt::from(other);
+    }
+}
+
+impl std::ops::Sub<i64> for &Fraction {
+    type Output = Fraction;
+    fn sub(self, other: i64) -> Fraction {
+        let mut result = self.clone();
+        result -= other;
+        result
+    }
+}
+
+impl std::ops::MulAssign<i64> for Fraction {
+    fn mul_assign(&mut self, other: i64) {
+        *self *= &utils::BigInt::from(other);
+    }
+}
+
+impl std::ops::Mul<i64> for &Fraction {
+    type Output = Fraction;
+    fn mul(self, other: i64) -> Fraction {
+        let mut result = self.clone();
+        result *= other;
+        result
+    }
+}
+
+impl std::ops::DivAssign<i64> for Fraction {
+    fn div_assign(&mut self, other: i64) {
+        *self /= &utils::BigInt::from(other);
+    }
+}
+
+impl std::ops::Div<i64> for &Fraction {
+    type Output = Fraction;
+    fn div(self, other: i64) -> Fraction {
+        let mut result = self.clone();
+        result /= other;
+        result
+    }
+}
+
+impl std::ops::AddAssign<&Fraction> for Fraction {
+    fn add_assign(&mut self, other: &Fraction) {
+        self.numerator = &self.scale_numerator(&other.denominator) + &other.scale_numerator(&self.denominator);
+        self.denominator = &self.denominator * &other.denominator;
+    }
+}
+
+impl std::ops::Add<&Fraction> for &Fraction {
+    type Output = Fraction;
+    fn add(self, other: &Fraction) -> Fraction {
+        let mut result = self.clone();
+        result += other;
+        result
+    }
+}
+
+impl std::ops::SubAssign<&Fraction> for Fraction {
+    fn sub_assign(&mut self, other: &Fraction) {
+        *self += &-other;
+    }
+}
+
+impl std::ops::Sub<&Fraction> for &Fraction {
+    type Output = Fraction;
+    fn sub(self, other: &Fraction) -> Fraction {
+        let mut result = self.clone();
+        result -= other;
+        result
+    }
+}
+
+impl std::ops::MulAssign<&Fraction> for Fraction {
+    fn mul_assign(&mut self, other: &Fraction) {
+        self.numerator *= &other.numerator;
+        self.denominator = &self.denominator * &other.denominator;
+    }
+}
+
+impl std::ops::Mul<&Fraction> for &Fraction {
+    type Output = Fraction;
+    fn mul(self, other: &Fraction) -> Fraction {
+        let mut result = self.clone();
+        result *= other;
+        result
+    }
+}
+
+impl std::ops::DivAssign<&Fraction> for Fraction {
+    fn div_assign(&mut self, other: &Fraction) {
+        if other.numerator.is_zero() {
+            panic!("division by zero");
+        }
+        self.numerator = utils::BigInt::from_parts(
+            self.numerator.is_negative() != other.numerator.is_negative(),
+            self.numerator.magnitude() * &other.denominator,
+        );
+        self.denominator = &self.denominator * other.numerator.magnitude();
+    }
+}
+
+impl std::ops::Div<&Fraction> for &Fraction {
+    type Output = Fraction;
+    fn div(self, other: &Fraction) -> Fraction {
+        let mut result = self.clone();
+        result /= other;
+        result
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Fraction) -> std::cmp::Ordering {
+        // Denominators are positive, so cross-multiplying preserves the
+        // order.
+        self.scale_numerator(&other.denominator)
+            .cmp(&other.scale_numerator(&self.denominator))
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Fraction) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Fraction {
+    fn eq(&self, other: &Fraction) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Fraction {}
+
 impl std::fmt::Display for Fraction {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{}/{}", self.numerator, self.denominator)
     }
 }
+
+impl std::fmt::Debug for Fraction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
@@ -0,0 +1,196 @@
+/// Linear sieve (also called the sieve of Euler), which finds the smallest
+/// prime factor of every number up to a limit. Each composite number is
+/// crossed out exactly once: by its smallest prime factor. Knowing the
+/// smallest prime factors, any number in range can be factorised by repeated
+/// division, and arithmetic functions can be tabulated in a single pass.
+pub struct LinearSieve {
+    limit: usize,
+    // Smallest prime factor of each number. (0 for 0 and 1.)
+    smallest_prime_factors: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl LinearSieve {
+    /// Construct the linear sieve up to and including the given number.
+    ///
+    /// * `limit` Non-strict upper bound. Must fit in 32 bits.
+    pub fn new(limit: usize) -> LinearSieve {
+        if u32::try_from(limit).is_err() {
+            panic!("limit of the linear sieve must fit in 32 bits");
+        }
+        let mut smallest_prime_factors = vec![0u32; limit + 1];
+        let mut primes = vec![];
+        for num in 2..=limit {
+            if smallest_prime_factors[num] == 0 {
+                smallest_prime_factors[num] = num as u32;
+                primes.push(num as u32);
+            }
+            // Cross out the multiples of this number by prime numbers not
+            // exceeding its smallest prime factor. Their smallest prime
+            // factors are those prime numbers.
+            let smallest_prime_factor = smallest_prime_factors[num];
+            for &prime in primes.iter().take_while(|&&prime| prime <= smallest_prime_factor) {
+                let multiple = num * prime as usize;
+                if multiple > limit {
+                    break;
+                }
+                smallest_prime_factors[multiple] = prime;
+            }
+        }
+        LinearSieve {
+            limit,
+            smallest_prime_factors,
+            primes,
+        }
+    }
+
+    /// Obtain the number the sieve was constructed up to.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Find the smallest prime factor of the given number.
+    ///
+    /// * `num` Must not exceed the limit of the sieve.
+    ///
+    /// Returns `None` if the number is 0 or 1.
+    pub fn smallest_prime_factor(&self, num: usize) -> Option<i64> {
+        if num > self.limit {
+            panic!("queried number is out of range of the sieve");
+        }
+        match self.smallest_prime_factors[num] {
+            0 => None,
+            smallest_prime_factor => Some(smallest_prime_factor as i64),
+        }
+    }
+
+    /// Check whether the given number is prime.
+    ///
+    /// * `num` Must not exceed the limit of the sieve.
+    pub fn is_prime(&self, num: usize) -> bool {
+        self.smallest_prime_factor(num) == Some(num as i64)
+    }
+
+    /// Yield the found prime numbers in order.
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        self.primes.iter().map(|&prime| prime as i64)
+    }
+
+    /// Factorise the given number. Each step divides it by its smallest prime
+    /// factor, so the number of steps is at most the number of bits in it.
+    ///
+    /// * `num` Must be positive and must not exceed the limit of the sieve.
+    ///
+    /// Returns an iterator over the prime factors (in ascending order) and
+    /// their exponents.
+    pub fn factorise(&self, mut num: usize) -> impl Iterator<Item = (i64, u32)> + '_ {
+        if num == 0 || num > self.limit {
+            panic!("queried number is out of range of the sieve");
+        }
+        std::iter::from_fn(move || {
+            let prime = self.smallest_prime_factors[num] as usize;
+            if prime == 0 {
+                return None;
+            }
+            let mut exponent = 0;
+            while num.is_multiple_of(prime) {
+                num /= prime;
+                exponent += 1;
+            }
+            Some((prime as i64, exponent))
+        })
+    }
+
+    /// Tabulate Euler's totient function: the number of positive integers up
+    /// to each number which are coprime to it.
+    ///
+    /// Returns a vector whose element at each index (from 1 to the limit of
+    /// the sieve) is the value at that index. The element at index 0 is 0.
+    pub fn totients(&self) -> Vec<i64> {
+        self.tabulate(1, |prime, exponent| (prime - 1) * prime.pow(exponent - 1), |a, b| a * b)
+    }
+
+    /// Tabulate the Möbius function: 0 for each number divisible by a perfect
+    /// square other than 1, and 1 or -1 for the others, depending on whether
+    /// they have an even or odd number of prime factors. See `totients`.
+    pub fn mobius(&self) -> Vec<i8> {
+        self.tabulate(1, |_, exponent| if exponent == 1 { -1 } else { 0 }, |a, b| a * b)
+    }
+
+    /// Tabulate the number of distinct prime factors of each number. See
+    /// `totients`.
+    pub fn omega(&self) -> Vec<u8> {
+        self.tabulate(0, |_, _| 1, |a, b| a + b)
+    }
+
+    /// Tabulate the number of prime factors of each number, counted with
+    /// multiplicity. See `totients`.
+    pub fn big_omega(&self) -> Vec<u8> {
+        self.tabulate(0, |_, exponent| exponent as u8, |a, b| a + b)
+    }
+
+    /// Tabulate the number of divisors of each number. See `totients`.
+    pub fn divisor_counts(&self) -> Vec<u32> {
+        self.tabulate(1, |_, exponent| exponent + 1, |a, b| a * b)
+    }
+
+    /// Tabulate the sum of some power of the divisors of each number. See
+    /// `totients`. Panics if any sum cannot be represented.
+    ///
+    /// * `k` Power.
+    pub fn divisor_sums(&self, k: u32) -> Vec<u64> {
+        self.tabulate(
+            1,
+            |prime, exponent| {
+                let prime_power = (prime as u64).checked_pow(k).expect("overflow detected");
+                (0..exponent).fold(1u64, |sum, _| {
+                    sum.checked_mul(prime_power)
+                        .and_then(|sum| sum.checked_add(1))
+                        .expect("overflow detected")
+                })
+            },
+            |a, b| a.checked_mul(b).expect("overflow detected"),
+        )
+    }
+
+    /// Tabulate an arithmetic function whose value at a number is obtained by
+    /// combining its values at the largest power of the smallest prime factor
+    /// and at the remaining part. (Multiplicative and additive functions are
+    /// such functions.) The largest power of the smallest prime factor of
+    /// each number is found from that of the number divided by its smallest
+    /// prime factor, so the whole table is generated in one pass.
+    ///
+    /// * `identity` Value at 1.
+    /// * `prime_power` Value at a prime power, given the prime and the
+    ///   exponent.
+    /// * `combine` Combination of the values at two coprime numbers.
+    fn tabulate<T: Copy + Default>(
+        &self,
+        identity: T,
+        prime_power: impl Fn(i64, u32) -> T,
+        combine: impl Fn(T, T) -> T,
+    ) -> Vec<T> {
+        let mut table = vec![T::default(); self.limit + 1];
+        // Largest power of the smallest prime factor of each number, and its
+        // exponent.
+        let mut powers = vec![0u32; self.limit + 1];
+        let mut exponents = vec![0u8; self.limit + 1];
+        if self.limit >= 1 {
+            table[1] = identity;
+        }
+        for num in 2..=self.limit {
+            let prime = self.smallest_prime_factors[num];
+            let quotient = num / prime as usize;
+            (powers[num], exponents[num]) = if self.smallest_prime_factors[quotient] == prime {
+                (powers[quotient] * prime, exponents[quotient] + 1)
+            } else {
+                (prime, 1)
+            };
+            table[num] = combine(
+                table[num / powers[num] as usize],
+                prime_power(prime as i64, exponents[num] as u32),
+            );
+        }
+        table
+    }
+}
@@ -5,42 +5,47 @@ use crate::utils;
 
 /// Arbitrary-precision integer type which stores digits of a positive number
 /// in base 1_000_000_000.
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, Hash, PartialEq)]
 pub struct Long {
     digits: Vec<u32>,
 }
 impl Long {
     /// Construct an arbitrary-precision integer.
     ///
-    /// * `s` Big-endian string of decimal digits.
+    /// * `s` Big-endian string of decimal digits. Panics if it cannot be
+    ///   parsed. Use `str::parse` to handle errors instead.
     pub fn new(s: &str) -> Long {
-        let mut long = Long { digits: vec![] };
-        let mut idx = s.len();
-        loop {
-            let (lower, upper) = (std::cmp::max(idx, 9) - 9, idx);
-            long.digits.push(s[lower..upper].parse().unwrap());
-            if lower == 0 {
-                break;
-            }
-            idx = lower;
-        }
-        long
+        s.parse().unwrap()
     }
 
-    /// Construct an arbitrary-precision integer.
-    ///
-    /// * `digit` Least significant digit in base 1_000_000_000.
-    pub fn from(digit: u32) -> Long {
-        if digit >= 1_000_000_000 {
-            panic!("argument is too large to be a digit of this arbitrary-precision type");
-        }
-        Long { digits: vec![digit] }
+    /// Construct an arbitrary-precision integer equal to zero.
+    pub fn zero() -> Long {
+        Long { digits: vec![0] }
+    }
+
+    /// Construct an arbitrary-precision integer equal to one.
+    pub fn one() -> Long {
+        Long { digits: vec![1] }
     }
 
     /// Construct an arbitrary-precision integer whose decimal representation
     /// is the reverse of the current one.
-    pub fn reverse(&self) -> Long {
-        Long::new(&self.to_string().chars().rev().collect::<String>())
+    pub fn reversed(&self) -> Long {
+        let mut result = self.clone();
+        result.reverse();
+        result
+    }
+
+    /// Reverse the decimal representation of this number in place. Leading
+    /// zeros of the result are discarded.
+    pub fn reverse(&mut self) {
+        let len = self.len();
+        let mut digits = vec![0; len.div_ceil(9)];
+        for (idx, digit) in self.digits().rev().enumerate() {
+            digits[idx / 9] += digit as u32 * Long::POWERS_OF_10[idx % 9];
+        }
+        self.digits = digits;
+        self.normalise();
     }
 
     /// Calculate the factorial of a non-negative number.
@@ -48,10 +53,10 @@ impl Long {
     /// * `num` Number whose factorial is to be calculated.
     pub fn factorial(num: u32) -> Long {
         if num == 0 || num == 1 {
-            return Long::from(1);
+            return Long::one();
         }
         if num == 2 {
-            return Long::from(2);
+            return Long::from(2u32);
         }
 
         // Multiply the extremes, converging towards the centre. For example,
@@ -61,7 +66,7 @@ impl Long {
         let mut result = if num % 2 == 1 {
             Long::from(partials + 1)
         } else {
-            Long::from(1)
+            Long::one()
         };
         let (mut multiplicand, mut delta) = (num, num);
         for _ in 0..partials {
@@ -72,6 +77,112 @@ impl Long {
         result
     }
 
+    /// Calculate a binomial coefficient. The exponent of each prime in it is
+    /// found using Legendre's formula, so no division of large numbers is
+    /// necessary.
+    ///
+    /// * `n` Number of items.
+    /// * `k` Number of items to choose.
+    pub fn binomial(n: u32, k: u32) -> Long {
+        if k > n {
+            return Long::zero();
+        }
+        let k = std::cmp::min(k, n - k);
+        let sieve_of_atkin = utils::SieveOfAtkin::new(n as usize);
+        let prime_powers = sieve_of_atkin.iter().map(|prime| {
+            let prime = prime as u32;
+            let (mut exp, mut power) = (0, prime as u64);
+            while power <= n as u64 {
+                let power_ = power as u32;
+                exp += n / power_ - k / power_ - (n - k) / power_;
+                power *= prime as u64;
+            }
+            // By Kummer's theorem, this does not exceed `n`.
+            prime.pow(exp)
+        });
+        Long::product_of(prime_powers)
+    }
+
+    /// Calculate a Fibonacci number. (This and the next Fibonacci number are
+    /// found using the fast doubling method.)
+    ///
+    /// * `n` Index of the Fibonacci number, starting from 0.
+    pub fn fibonacci(n: u32) -> Long {
+        Long::fibonacci_pair(n).0
+    }
+
+    /// Calculate a Lucas number.
+    ///
+    /// * `n` Index of the Lucas number, starting from 0.
+    pub fn lucas(n: u32) -> Long {
+        let (curr, next) = Long::fibonacci_pair(n);
+        &(&next * 2) - &curr
+    }
+
+    /// Calculate two consecutive Fibonacci numbers using the identities
+    /// F(2k) = F(k) × (2F(k + 1) − F(k)) and F(2k + 1) = F(k)² + F(k + 1)².
+    ///
+    /// * `n` Index of the first Fibonacci number.
+    fn fibonacci_pair(n: u32) -> (Long, Long) {
+        let (mut curr, mut next) = (Long::zero(), Long::one());
+        for shift in (0..u32::BITS - n.leading_zeros()).rev() {
+            let double = &curr * &(&(&next * 2) - &curr);
+            let double_next = &curr.square() + &next.square();
+            (curr, next) = if n >> shift & 1 == 1 {
+                let double_next_next = &double + &double_next;
+                (double_next, double_next_next)
+            } else {
+                (double, double_next)
+            };
+        }
+        (curr, next)
+    }
+
+    /// Calculate a Catalan number.
+    ///
+    /// * `n` Index of the Catalan number, starting from 0.
+    pub fn catalan(n: u32) -> Long {
+        &Long::binomial(2 * n, n) / (n + 1)
+    }
+
+    /// Calculate the primorial of a number: the product of all prime numbers
+    /// not exceeding it.
+    ///
+    /// * `n`
+    pub fn primorial(n: u32) -> Long {
+        Long::product_of(utils::SieveOfAtkin::new(n as usize).iter().map(|prime| prime as u32))
+    }
+
+    /// Multiply many small numbers. Numbers are first multiplied in machine
+    /// words for as long as possible, and the partial products are then
+    /// multiplied pairwise, so that the operands of each multiplication have
+    /// similar lengths. This is much faster than multiplying them one at a
+    /// time when the product is large.
+    ///
+    /// * `factors`
+    fn product_of(factors: impl Iterator<Item = u32>) -> Long {
+        let mut partials = vec![];
+        let mut partial = 1u64;
+        for factor in factors {
+            if partial * factor as u64 >= 1_000_000_000 {
+                partials.push(Long::from(partial));
+                partial = 1;
+            }
+            partial *= factor as u64;
+        }
+        partials.push(Long::from(partial));
+        while partials.len() > 1 {
+            partials = partials
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => a * b,
+                    _ => pair[0].clone(),
+                })
+                .collect();
+        }
+        partials.pop().unwrap()
+    }
+
     /// Obtain the number of decimal digits of this number (i.e. its length).
     pub fn len(&self) -> usize {
         match self.digits.len() {
@@ -81,6 +192,90 @@ impl Long {
         }
     }
 
+    /// Obtain the decimal digit at the given position.
+    ///
+    /// * `idx` Position, counted from the least significant digit.
+    fn digit(&self, idx: usize) -> i64 {
+        (self.digits[idx / 9] / Long::POWERS_OF_10[idx % 9] % 10) as i64
+    }
+
+    /// Generate the decimal digits of this number from least significant to
+    /// most significant. Reverse the iterator to generate them from most
+    /// significant to least significant. Mirrors `utils::Digits`.
+    pub fn digits(&self) -> impl DoubleEndedIterator<Item = i64> + ExactSizeIterator + '_ {
+        (0..self.len()).map(|idx| self.digit(idx))
+    }
+
+    /// Determine the number of times each decimal digit appears in this
+    /// number. Mirrors `utils::digits_frequencies`.
+    ///
+    /// Returns an array in which each element is the number of occurrences of
+    /// its index.
+    pub fn digit_frequencies(&self) -> [usize; 10] {
+        let mut frequency = [0; 10];
+        for digit in self.digits() {
+            frequency[digit as usize] += 1;
+        }
+        frequency
+    }
+
+    /// Check whether the decimal representation of this number is a
+    /// palindrome.
+    pub fn is_palindrome(&self) -> bool {
+        self.digits()
+            .zip(self.digits().rev())
+            .take(self.len() / 2)
+            .all(|(a, b)| a == b)
+    }
+
+    /// Obtain the given number of most significant decimal digits of this
+    /// number.
+    ///
+    /// * `count`
+    pub fn leading_digits(&self, count: usize) -> Long {
+        let mut result = self.clone();
+        result.truncate_right(self.len().saturating_sub(count));
+        result
+    }
+
+    /// Obtain the given number of least significant decimal digits of this
+    /// number.
+    ///
+    /// * `count`
+    pub fn trailing_digits(&self, count: usize) -> Long {
+        let mut result = self.clone();
+        result.truncate_left(self.len().saturating_sub(count));
+        result
+    }
+
+    /// Remove the given number of most significant decimal digits of this
+    /// number. Leading zeros of the result are discarded.
+    ///
+    /// * `count`
+    pub fn truncate_left(&mut self, count: usize) {
+        let keep = self.len().saturating_sub(count);
+        self.digits.truncate(keep.div_ceil(9));
+        if !keep.is_multiple_of(9) {
+            *self.digits.last_mut().unwrap() %= Long::POWERS_OF_10[keep % 9];
+        }
+        self.normalise();
+    }
+
+    /// Remove the given number of least significant decimal digits of this
+    /// number.
+    ///
+    /// * `count`
+    pub fn truncate_right(&mut self, count: usize) {
+        if count >= self.len() {
+            *self = Long::zero();
+            return;
+        }
+        self.digits.drain(..count / 9);
+        if !count.is_multiple_of(9) {
+            *self = self.div_rem_u32(Long::POWERS_OF_10[count % 9]).0;
+        }
+    }
+
     /// Calculate the sum of all decimal digits of this number.
     pub fn sum(&self) -> i64 {
         self.digits
@@ -89,13 +284,37 @@ impl Long {
             .sum()
     }
 
+    /// Approximate this number by a floating-point number.
+    ///
+    /// Returns infinity if this number is too large to be represented.
+    pub fn to_f64(&self) -> f64 {
+        self.digits
+            .iter()
+            .rev()
+            .fold(0.0, |result, &digit| result * 1_000_000_000.0 + digit as f64)
+    }
+
+    /// Approximate the common logarithm of this number. Unlike `to_f64`,
+    /// this is finite for any positive number.
+    ///
+    /// Returns negative infinity if this number is zero.
+    pub fn log10(&self) -> f64 {
+        // Only the three most significant digits in base 1_000_000_000 affect
+        // the result at double precision.
+        let skipped = self.digits.len().saturating_sub(3);
+        let leading = Long {
+            digits: self.digits[skipped..].to_vec(),
+        };
+        leading.to_f64().log10() + (skipped * 9) as f64
+    }
+
     /// Raise this number to the given power.
     ///
     /// * `exp` Power.
     pub fn pow(&self, mut exp: u32) -> Long {
         // Multiplication is expensive, so these checks will improve
         // performance.
-        let mut multiplier = Long::from(1);
+        let mut multiplier = Long::one();
         if exp == 0 {
             return multiplier;
         }
@@ -111,8 +330,256 @@ impl Long {
                 return multiplier;
             }
             exp /= 2;
-            base = &base * &base;
+            base = base.square();
+        }
+    }
+
+    /// Square this number. This is faster than multiplying it by itself.
+    pub fn square(&self) -> Long {
+        let mut result = Long {
+            digits: Long::sqr_digits(&self.digits),
+        };
+        result.normalise();
+        result
+    }
+
+    /// Calculate the greatest common divisor of this number and another
+    /// number.
+    ///
+    /// * `other`
+    pub fn gcd(&self, other: &Long) -> Long {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let rem = &a % &b;
+            (a, b) = (b, rem);
+        }
+        a
+    }
+
+    /// Calculate the least common multiple of this number and another number.
+    ///
+    /// * `other`
+    pub fn lcm(&self, other: &Long) -> Long {
+        if self.is_zero() || other.is_zero() {
+            return Long::zero();
+        }
+        &(self / &self.gcd(other)) * other
+    }
+
+    /// Perform modular exponentiation.
+    ///
+    /// * `exp` Exponent.
+    /// * `modulus` Must be positive.
+    pub fn pow_mod(&self, exp: &Long, modulus: &Long) -> Long {
+        if modulus.is_zero() {
+            panic!("division by zero");
+        }
+        let base = self % modulus;
+        let mut result = &Long::one() % modulus;
+
+        // Scan the bits of the exponent from the most significant one.
+        for &word in exp.binary_digits().iter().rev() {
+            for shift in (0..32).rev() {
+                result = &result.square() % modulus;
+                if word >> shift & 1 == 1 {
+                    result = &(&result * &base) % modulus;
+                }
+            }
+        }
+        result
+    }
+
+    /// Calculate the square root of this number, rounded down.
+    pub fn isqrt(&self) -> Long {
+        self.nth_root(2)
+    }
+
+    /// Calculate the root of the given degree of this number, rounded down.
+    ///
+    /// * `degree` Must be positive.
+    pub fn nth_root(&self, degree: u32) -> Long {
+        if degree == 0 {
+            panic!("zeroth root is undefined");
+        }
+        if degree == 1 || self.is_zero() {
+            return self.clone();
+        }
+
+        // Newton's method converges to the root from above, so start from a
+        // power of 10 which is definitely not smaller than the root.
+        let mut root = Long::from(10u32).pow((self.len() as u32).div_ceil(degree));
+        loop {
+            let mut next = self / &root.pow(degree - 1);
+            next += &(&root * (degree - 1));
+            next /= degree;
+            if next >= root {
+                return root;
+            }
+            root = next;
+        }
+    }
+
+    /// Represent this number as a string in the given base.
+    ///
+    /// * `radix` Must be between 2 and 36 (both inclusive). Digits greater
+    ///   than 9 are represented using lowercase letters.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        if !(2..=36).contains(&radix) {
+            panic!("radix must be between 2 and 36");
+        }
+        if radix == 10 {
+            return self.to_string();
+        }
+
+        // Extract as many digits in the given base at once as will fit in a
+        // machine word.
+        let (chunk_len, chunk_radix) = Long::chunk(radix);
+        let mut chunks = vec![];
+        let mut num = self.clone();
+        loop {
+            let (quotient, rem) = num.div_rem_u32(chunk_radix);
+            chunks.push(rem);
+            if quotient.is_zero() {
+                break;
+            }
+            num = quotient;
+        }
+        let mut s = String::new();
+        for (idx, &chunk) in chunks.iter().rev().enumerate() {
+            let chunk = (0..chunk_len)
+                .scan(chunk, |chunk, _| {
+                    let digit = char::from_digit(*chunk % radix, radix);
+                    *chunk /= radix;
+                    digit
+                })
+                .collect::<Vec<char>>();
+            let chunk = chunk.iter().rev().collect::<String>();
+            s.push_str(if idx == 0 {
+                chunk.trim_start_matches('0')
+            } else {
+                &chunk
+            });
+        }
+        if s.is_empty() {
+            s.push('0');
+        }
+        s
+    }
+
+    /// Construct an arbitrary-precision integer from its representation in
+    /// the given base.
+    ///
+    /// * `s` Big-endian string of digits, optionally preceded by a plus sign.
+    ///   Letters of either case are accepted for digits greater than 9.
+    /// * `radix` Must be between 2 and 36 (both inclusive).
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Long, utils::ParseLongError> {
+        if !(2..=36).contains(&radix) {
+            panic!("radix must be between 2 and 36");
+        }
+        if s.starts_with('-') {
+            return Err(utils::ParseLongError::Negative);
+        }
+        let s = s.strip_prefix('+').unwrap_or(s);
+        if s.is_empty() {
+            return Err(utils::ParseLongError::Empty);
+        }
+        let digits = s
+            .chars()
+            .map(|c| c.to_digit(radix).ok_or(utils::ParseLongError::InvalidDigit))
+            .collect::<Result<Vec<u32>, utils::ParseLongError>>()?;
+        let (chunk_len, _) = Long::chunk(radix);
+        let mut result = Long::zero();
+        for chunk in digits.chunks(chunk_len) {
+            result *= radix.pow(chunk.len() as u32);
+            result += &Long::from(chunk.iter().fold(0, |value, digit| value * radix + digit));
+        }
+        Ok(result)
+    }
+
+    /// Determine how many digits in the given base fit in a machine word.
+    ///
+    /// * `radix`
+    ///
+    /// Returns the number of digits and the base raised to that number.
+    fn chunk(radix: u32) -> (usize, u32) {
+        let chunk_len = u32::MAX.ilog(radix);
+        (chunk_len as usize, radix.pow(chunk_len))
+    }
+
+    /// Construct an arbitrary-precision integer from its digits in base
+    /// 4_294_967_296.
+    ///
+    /// * `digits` Digits from least significant to most significant.
+    fn from_binary_digits(digits: &[u32]) -> Long {
+        let mut result = Long::zero();
+        for &digit in digits.iter().rev() {
+            result *= 1 << 16;
+            result *= 1 << 16;
+            result += &Long::from(digit);
+        }
+        result
+    }
+
+    /// Combine the binary representations of this number and another number
+    /// bitwise.
+    ///
+    /// * `other`
+    /// * `oper` Function to apply to corresponding digits in base
+    ///   4_294_967_296.
+    fn bitwise(&self, other: &Long, oper: fn(u32, u32) -> u32) -> Long {
+        let (mut sd, mut od) = (self.binary_digits(), other.binary_digits());
+        let len = std::cmp::max(sd.len(), od.len());
+        sd.resize(len, 0);
+        od.resize(len, 0);
+        let digits = sd
+            .iter()
+            .zip(od.iter())
+            .map(|(&s, &o)| oper(s, o))
+            .collect::<Vec<u32>>();
+        Long::from_binary_digits(&digits)
+    }
+
+    /// Count the number of ones in the binary representation of this number.
+    pub fn count_ones(&self) -> u32 {
+        self.binary_digits().iter().map(|digit| digit.count_ones()).sum()
+    }
+
+    /// Obtain the number of binary digits of this number. This is zero if
+    /// the number is zero.
+    pub fn bit_length(&self) -> u64 {
+        let digits = self.binary_digits();
+        match digits.last() {
+            None => 0,
+            Some(last) => (digits.len() as u64 - 1) * 32 + (32 - last.leading_zeros()) as u64,
+        }
+    }
+
+    /// Generate the binary digits of this number from least significant to
+    /// most significant. Mirrors `utils::Bits`.
+    pub fn bits(&self) -> impl Iterator<Item = i64> {
+        let bit_length = self.bit_length();
+        self.binary_digits()
+            .into_iter()
+            .flat_map(|digit| (0..32).map(move |shift| (digit >> shift & 1) as i64))
+            .take(bit_length as usize)
+    }
+
+    /// Obtain the digits of this number in base 4_294_967_296.
+    ///
+    /// Returns the digits from least significant to most significant.
+    fn binary_digits(&self) -> Vec<u32> {
+        let mut digits = vec![];
+        let mut num = self.clone();
+        while !num.is_zero() {
+            let mut rem = 0u64;
+            for sd in num.digits.iter_mut().rev() {
+                let dividend = rem * 1_000_000_000 + *sd as u64;
+                (*sd, rem) = ((dividend >> 32) as u32, dividend & 0xFFFF_FFFF);
+            }
+            num.normalise();
+            digits.push(rem as u32);
         }
+        digits
     }
 
     /// Add two digits in base 1_000_000_000.
@@ -143,76 +610,1219 @@ impl Long {
         let product = a as u64 * b as u64 + carry as u64;
         ((product % 1_000_000_000) as u32, (product / 1_000_000_000) as u32)
     }
-}
 
-impl std::ops::AddAssign<&Long> for Long {
-    fn add_assign(&mut self, other: &Long) {
-        self.digits
-            .resize(std::cmp::max(self.digits.len(), other.digits.len()), 0);
-        let mut carry = false;
-        for (sd, od) in self
-            .digits
-            .iter_mut()
-            .zip(other.digits.iter().chain(std::iter::repeat(&0)))
-        {
-            (*sd, carry) = Long::adc(*sd, *od, carry);
+    /// Subtract two digits in base 1_000_000_000.
+    ///
+    /// * `a`
+    /// * `b`
+    /// * `borrow` Borrowed unit to be subtracted.
+    ///
+    /// Returns the difference of the digits in base 1_000_000_000 and the new
+    /// borrow.
+    fn sbb(a: u32, b: u32, borrow: bool) -> (u32, bool) {
+        let subtrahend = b + borrow as u32;
+        if a >= subtrahend {
+            (a - subtrahend, false)
+        } else {
+            (a + 1_000_000_000 - subtrahend, true)
         }
-        if carry {
-            self.digits.push(1);
+    }
+
+    /// Remove the leading zeros of this number, keeping at least one digit.
+    fn normalise(&mut self) {
+        while self.digits.len() > 1 && *self.digits.last().unwrap() == 0 {
+            self.digits.pop();
+        }
+        if self.digits.is_empty() {
+            self.digits.push(0);
         }
     }
-}
 
-impl std::ops::Add<&Long> for &Long {
-    type Output = Long;
-    fn add(self, other: &Long) -> Long {
-        let mut result = self.clone();
-        result += other;
-        result
+    /// Check whether this number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.digits.iter().all(|&digit| digit == 0)
     }
-}
 
-impl std::ops::MulAssign<u32> for Long {
-    fn mul_assign(&mut self, other: u32) {
-        let mut carry = 0;
-        for sd in self.digits.iter_mut() {
-            (*sd, carry) = Long::mlc(*sd, other, carry);
+    /// Split a number into digits in base 1_000_000_000.
+    ///
+    /// * `num`
+    fn split(num: u32) -> [u32; 2] {
+        [num % 1_000_000_000, num / 1_000_000_000]
+    }
+
+    /// Subtract the given digits from this number in place.
+    ///
+    /// * `other` Little-endian digits in base 1_000_000_000. Must not
+    ///   represent a number exceeding this number.
+    fn sub_digits(&mut self, other: &[u32]) {
+        if other.iter().skip(self.digits.len()).any(|&od| od != 0) {
+            panic!("underflow detected; subtrahend exceeds minuend");
         }
-        while carry > 0 {
-            self.digits.push(carry % 1_000_000_000);
-            carry /= 1_000_000_000;
+        let mut borrow = false;
+        for (idx, sd) in self.digits.iter_mut().enumerate() {
+            if idx >= other.len() && !borrow {
+                break;
+            }
+            let od = other.get(idx).copied().unwrap_or(0);
+            (*sd, borrow) = Long::sbb(*sd, od, borrow);
         }
+        if borrow {
+            panic!("underflow detected; subtrahend exceeds minuend");
+        }
+        self.normalise();
     }
-}
 
-impl std::ops::Mul<u32> for &Long {
-    type Output = Long;
-    fn mul(self, other: u32) -> Long {
+    /// Subtract the given number from this number.
+    ///
+    /// * `other`
+    ///
+    /// Returns the difference if it is non-negative. Returns `None` otherwise.
+    pub fn checked_sub(&self, other: &Long) -> Option<Long> {
+        if self < other {
+            return None;
+        }
         let mut result = self.clone();
-        result *= other;
-        result
+        result.sub_digits(&other.digits);
+        Some(result)
     }
-}
 
-impl std::ops::Mul<&Long> for &Long {
-    type Output = Long;
-    fn mul(self, other: &Long) -> Long {
-        let mut result = Long { digits: vec![] };
-        for (pad, od) in other.digits.iter().enumerate() {
-            let mut partial_product = Long { digits: vec![0; pad] };
-            let mut carry = 0;
-            for sd in self.digits.iter() {
-                let sum_carry = Long::mlc(*sd, *od, carry);
-                partial_product.digits.push(sum_carry.0);
-                carry = sum_carry.1;
+    /// Divide this number by a number which fits in a machine word.
+    ///
+    /// * `other` Divisor. Must be positive.
+    ///
+    /// Returns the quotient and remainder.
+    fn div_rem_u32(&self, other: u32) -> (Long, u32) {
+        if other == 0 {
+            panic!("division by zero");
+        }
+        let mut quotient = Long {
+            digits: vec![0; self.digits.len()],
+        };
+        let mut rem = 0u64;
+        for (qd, sd) in quotient.digits.iter_mut().zip(self.digits.iter()).rev() {
+            let dividend = rem * 1_000_000_000 + *sd as u64;
+            (*qd, rem) = ((dividend / other as u64) as u32, dividend % other as u64);
+        }
+        quotient.normalise();
+        (quotient, rem as u32)
+    }
+
+    /// Divide this number by another number. This is long division in base
+    /// 1_000_000_000 (Knuth's Algorithm D).
+    ///
+    /// * `other` Divisor.
+    ///
+    /// Returns the quotient and remainder if the divisor is non-zero. Returns
+    /// `None` otherwise.
+    pub fn checked_div_rem(&self, other: &Long) -> Option<(Long, Long)> {
+        if other.is_zero() {
+            return None;
+        }
+        if self < other {
+            return Some((Long::zero(), self.clone()));
+        }
+        if other.digits.len() == 1 {
+            let (quotient, rem) = self.div_rem_u32(other.digits[0]);
+            return Some((quotient, Long::from(rem)));
+        }
+
+        // Scale both numbers so that the most significant digit of the
+        // divisor is at least half the base. This keeps the estimated
+        // quotient digits within two of the true ones.
+        let scale = 1_000_000_000 / (*other.digits.last().unwrap() + 1);
+        let dividend = self * scale;
+        let divisor = other * scale;
+        let mut un = dividend.digits;
+        un.resize(self.digits.len() + 1, 0);
+        let vn = divisor.digits;
+        let (n, m) = (vn.len(), un.len() - vn.len() - 1);
+        let mut quotient = Long { digits: vec![0; m + 1] };
+        for j in (0..=m).rev() {
+            let numerator = un[j + n] as u64 * 1_000_000_000 + un[j + n - 1] as u64;
+            let (mut qhat, mut rhat) = (numerator / vn[n - 1] as u64, numerator % vn[n - 1] as u64);
+            while qhat >= 1_000_000_000 || qhat * vn[n - 2] as u64 > rhat * 1_000_000_000 + un[j + n - 2] as u64 {
+                qhat -= 1;
+                rhat += vn[n - 1] as u64;
+                if rhat >= 1_000_000_000 {
+                    break;
+                }
             }
-            if carry > 0 {
-                partial_product.digits.push(carry);
+
+            // Multiply and subtract. If the result is negative, the estimate
+            // was one too large, so add the divisor back.
+            let (mut carry, mut borrow) = (0u64, false);
+            for i in 0..n {
+                let product = qhat * vn[i] as u64 + carry;
+                carry = product / 1_000_000_000;
+                (un[i + j], borrow) = Long::sbb(un[i + j], (product % 1_000_000_000) as u32, borrow);
+            }
+            let top = un[j + n] as i64 - borrow as i64 - carry as i64;
+            if top < 0 {
+                un[j + n] = (top + 1_000_000_000) as u32;
+                qhat -= 1;
+                let mut carry = false;
+                for i in 0..n {
+                    (un[i + j], carry) = Long::adc(un[i + j], vn[i], carry);
+                }
+                un[j + n] = (un[j + n] + carry as u32) % 1_000_000_000;
+            } else {
+                un[j + n] = top as u32;
             }
-            result += &partial_product;
+            quotient.digits[j] = qhat as u32;
         }
+        quotient.normalise();
+        un.truncate(n);
+        let mut rem = Long { digits: un };
+        rem.normalise();
+        Some((quotient, rem.div_rem_u32(scale).0))
+    }
+
+    /// Divide this number by another number.
+    ///
+    /// * `other` Divisor. Must be non-zero.
+    ///
+    /// Returns the quotient and remainder.
+    pub fn div_rem(&self, other: &Long) -> (Long, Long) {
+        self.checked_div_rem(other).expect("division by zero")
+    }
+}
+
+impl Long {
+    // Place values of the decimal digits of a digit in base 1_000_000_000.
+    const POWERS_OF_10: [u32; 9] = [1, 10, 100, 1000, 10000, 100000, 1000000, 10000000, 100000000];
+    // Operands shorter than this (in base 1_000_000_000) are multiplied using
+    // the schoolbook method, because the bookkeeping of Karatsuba's method
+    // costs more than it saves.
+    const KARATSUBA_THRESHOLD: usize = 40;
+    // Operands at least this long are multiplied using the number-theoretic
+    // transform, provided that the product is not too long for it.
+    const NTT_THRESHOLD: usize = 4000;
+    // Largest transform length supported by all three primes below.
+    const NTT_MAX_LEN: usize = 1 << 23;
+    // Primes of the form k × 2^n + 1 and their primitive roots. The product
+    // of the three exceeds the largest possible coefficient of the
+    // convolution of two sequences of length `NTT_MAX_LEN` with digits in
+    // base 1_000_000_000, so each coefficient can be recovered exactly using
+    // the Chinese remainder theorem.
+    const NTT_PRIMES: [(u64, u64); 3] = [(998244353, 3), (167772161, 3), (469762049, 3)];
+
+    /// Multiply this number by another number using the schoolbook method.
+    /// Exposed so that the multiplication strategies can be benchmarked
+    /// against each other; the `*` operator chooses one automatically.
+    ///
+    /// * `other`
+    pub fn mul_schoolbook(&self, other: &Long) -> Long {
+        let mut result = Long {
+            digits: Long::schoolbook_mul(&self.digits, &other.digits),
+        };
+        result.normalise();
+        result
+    }
+
+    /// Multiply this number by another number using Karatsuba's method,
+    /// falling back to the schoolbook method for short operands.
+    ///
+    /// * `other`
+    pub fn mul_karatsuba(&self, other: &Long) -> Long {
+        let mut result = Long {
+            digits: Long::mul_digits(&self.digits, &other.digits, false),
+        };
+        result.normalise();
         result
     }
+
+    /// Multiply this number by another number using the number-theoretic
+    /// transform.
+    ///
+    /// * `other` Must be such that the product has at most 75_497_472
+    ///   decimal digits.
+    pub fn mul_ntt(&self, other: &Long) -> Long {
+        let mut result = Long {
+            digits: Long::ntt_mul(&self.digits, &other.digits),
+        };
+        result.normalise();
+        result
+    }
+
+    /// Remove the leading zeros of a little-endian sequence of digits.
+    ///
+    /// * `digits`
+    fn trim(digits: &[u32]) -> &[u32] {
+        let len = digits.iter().rposition(|&digit| digit != 0).map_or(0, |idx| idx + 1);
+        &digits[..len]
+    }
+
+    /// Multiply two little-endian sequences of digits in base 1_000_000_000
+    /// using the fastest applicable method.
+    ///
+    /// * `a`
+    /// * `b`
+    /// * `ntt` Whether the number-theoretic transform may be used.
+    fn mul_digits(a: &[u32], b: &[u32], ntt: bool) -> Vec<u32> {
+        let (a, b) = (Long::trim(a), Long::trim(b));
+        let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+        if b.len() < Long::KARATSUBA_THRESHOLD {
+            Long::schoolbook_mul(a, b)
+        } else if ntt && b.len() >= Long::NTT_THRESHOLD && a.len() + b.len() <= Long::NTT_MAX_LEN {
+            Long::ntt_mul(a, b)
+        } else {
+            Long::karatsuba_mul(a, b, ntt)
+        }
+    }
+
+    /// Square a little-endian sequence of digits in base 1_000_000_000 using
+    /// the fastest applicable method.
+    ///
+    /// * `a`
+    fn sqr_digits(a: &[u32]) -> Vec<u32> {
+        let a = Long::trim(a);
+        if a.len() < Long::KARATSUBA_THRESHOLD {
+            Long::schoolbook_sqr(a)
+        } else if a.len() >= Long::NTT_THRESHOLD && 2 * a.len() <= Long::NTT_MAX_LEN {
+            Long::ntt_sqr(a)
+        } else {
+            Long::karatsuba_sqr(a)
+        }
+    }
+
+    /// Multiply two sequences of digits using the schoolbook method.
+    ///
+    /// * `a`
+    /// * `b`
+    fn schoolbook_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut result = vec![0; a.len() + b.len()];
+        for (i, &ad) in a.iter().enumerate() {
+            if ad == 0 {
+                continue;
+            }
+            let mut carry = 0;
+            for (rd, &bd) in result[i..].iter_mut().zip(b.iter()) {
+                let sum = *rd as u64 + ad as u64 * bd as u64 + carry;
+                (*rd, carry) = ((sum % 1_000_000_000) as u32, sum / 1_000_000_000);
+            }
+            result[i + b.len()] = carry as u32;
+        }
+        result
+    }
+
+    /// Square a sequence of digits using the schoolbook method. Every product
+    /// of two different digits appears twice, so it is computed only once and
+    /// doubled.
+    ///
+    /// * `a`
+    fn schoolbook_sqr(a: &[u32]) -> Vec<u32> {
+        let mut result = vec![0; 2 * a.len()];
+        for (i, &ad) in a.iter().enumerate() {
+            let mut carry = 0;
+            for (rd, &bd) in result[2 * i + 1..].iter_mut().zip(a[i + 1..].iter()) {
+                let sum = *rd as u64 + 2 * ad as u64 * bd as u64 + carry;
+                (*rd, carry) = ((sum % 1_000_000_000) as u32, sum / 1_000_000_000);
+            }
+            result[i + a.len()] = carry as u32;
+        }
+        let mut carry = 0;
+        for (i, rd) in result.iter_mut().enumerate() {
+            let diagonal = if i % 2 == 0 {
+                a[i / 2] as u64 * a[i / 2] as u64
+            } else {
+                0
+            };
+            let sum = *rd as u64 + diagonal + carry;
+            (*rd, carry) = ((sum % 1_000_000_000) as u32, sum / 1_000_000_000);
+        }
+        result
+    }
+
+    /// Add two sequences of digits.
+    ///
+    /// * `a`
+    /// * `b`
+    fn add_digits(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut result = a.to_vec();
+        Long::add_shifted(&mut result, b, 0);
+        result
+    }
+
+    /// Add a sequence of digits to another after shifting it by some places.
+    ///
+    /// * `a` Augend, which will be extended if required.
+    /// * `b` Addend.
+    /// * `shift` Number of places (in base 1_000_000_000) to shift the addend
+    ///   by.
+    fn add_shifted(a: &mut Vec<u32>, b: &[u32], shift: usize) {
+        let b = Long::trim(b);
+        if a.len() < shift + b.len() {
+            a.resize(shift + b.len(), 0);
+        }
+        let mut carry = false;
+        for (ad, &bd) in a[shift..].iter_mut().zip(b.iter()) {
+            (*ad, carry) = Long::adc(*ad, bd, carry);
+        }
+        for ad in a[shift + b.len()..].iter_mut() {
+            if !carry {
+                break;
+            }
+            (*ad, carry) = Long::adc(*ad, 0, carry);
+        }
+        if carry {
+            a.push(1);
+        }
+    }
+
+    /// Subtract a sequence of digits from another in place.
+    ///
+    /// * `a` Minuend. Must not be smaller than the subtrahend.
+    /// * `b` Subtrahend.
+    fn sub_in_place(a: &mut [u32], b: &[u32]) {
+        let mut borrow = false;
+        for (idx, ad) in a.iter_mut().enumerate() {
+            if idx >= b.len() && !borrow {
+                break;
+            }
+            (*ad, borrow) = Long::sbb(*ad, b.get(idx).copied().unwrap_or(0), borrow);
+        }
+    }
+
+    /// Multiply two sequences of digits using Karatsuba's method.
+    ///
+    /// * `a` Must not be shorter than `b`.
+    /// * `b`
+    /// * `ntt` Whether the number-theoretic transform may be used to compute
+    ///   the partial products.
+    fn karatsuba_mul(a: &[u32], b: &[u32], ntt: bool) -> Vec<u32> {
+        let half = a.len() / 2;
+
+        // If the operands are unbalanced, splitting both at the same place is
+        // wasteful. Multiply the shorter operand by pieces of the longer one
+        // instead.
+        if b.len() <= half {
+            let mut result = vec![0; a.len() + b.len()];
+            for (idx, piece) in a.chunks(b.len()).enumerate() {
+                Long::add_shifted(&mut result, &Long::mul_digits(piece, b, ntt), idx * b.len());
+            }
+            return result;
+        }
+
+        // With the operands written as a1 × X + a0 and b1 × X + b0, the middle
+        // coefficient of the product is (a0 + a1) × (b0 + b1) − a0 × b0 −
+        // a1 × b1. This needs three multiplications instead of four.
+        let (a0, a1) = a.split_at(half);
+        let (b0, b1) = b.split_at(half);
+        let z0 = Long::mul_digits(a0, b0, ntt);
+        let z2 = Long::mul_digits(a1, b1, ntt);
+        let mut z1 = Long::mul_digits(&Long::add_digits(a0, a1), &Long::add_digits(b0, b1), ntt);
+        Long::sub_in_place(&mut z1, &z0);
+        Long::sub_in_place(&mut z1, &z2);
+        let mut result = vec![0; a.len() + b.len()];
+        Long::add_shifted(&mut result, &z0, 0);
+        Long::add_shifted(&mut result, &z1, half);
+        Long::add_shifted(&mut result, &z2, 2 * half);
+        result
+    }
+
+    /// Square a sequence of digits using Karatsuba's method.
+    ///
+    /// * `a`
+    fn karatsuba_sqr(a: &[u32]) -> Vec<u32> {
+        let half = a.len() / 2;
+        let (a0, a1) = a.split_at(half);
+        let z0 = Long::sqr_digits(a0);
+        let z2 = Long::sqr_digits(a1);
+        let mut z1 = Long::sqr_digits(&Long::add_digits(a0, a1));
+        Long::sub_in_place(&mut z1, &z0);
+        Long::sub_in_place(&mut z1, &z2);
+        let mut result = vec![0; 2 * a.len()];
+        Long::add_shifted(&mut result, &z0, 0);
+        Long::add_shifted(&mut result, &z1, half);
+        Long::add_shifted(&mut result, &z2, 2 * half);
+        result
+    }
+
+    /// Perform the number-theoretic transform in place.
+    ///
+    /// * `values` Residues modulo the prime. Length must be a power of 2.
+    /// * `invert` Whether to perform the inverse transform instead.
+    /// * `prime`
+    /// * `generator` Primitive root modulo the prime.
+    fn ntt(values: &mut [u64], invert: bool, prime: u64, generator: u64) {
+        let len = values.len();
+        let mut j = 0;
+        for i in 1..len {
+            let mut bit = len >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j ^= bit;
+            if i < j {
+                values.swap(i, j);
+            }
+        }
+        for width in (1..=len.trailing_zeros()).map(|power| 1 << power) {
+            let root = utils::pow(generator as i64, (prime - 1) / width as u64, prime as i64) as u64;
+            let root = if invert {
+                utils::pow(root as i64, prime - 2, prime as i64) as u64
+            } else {
+                root
+            };
+            for chunk in values.chunks_mut(width) {
+                let (lower, upper) = chunk.split_at_mut(width / 2);
+                let mut twiddle = 1;
+                for (u, v) in lower.iter_mut().zip(upper.iter_mut()) {
+                    let t = *v * twiddle % prime;
+                    (*u, *v) = ((*u + t) % prime, (*u + prime - t) % prime);
+                    twiddle = twiddle * root % prime;
+                }
+            }
+        }
+        if invert {
+            let len_inv = utils::pow(len as i64, prime - 2, prime as i64) as u64;
+            for value in values.iter_mut() {
+                *value = *value * len_inv % prime;
+            }
+        }
+    }
+
+    /// Recover the exact coefficients of a convolution from their residues
+    /// modulo the three primes, and propagate carries to obtain digits in
+    /// base 1_000_000_000.
+    ///
+    /// * `residues` Convolution modulo each prime.
+    /// * `len` Number of digits to produce.
+    fn ntt_reconstruct(residues: &[Vec<u64>; 3], len: usize) -> Vec<u32> {
+        let [(p1, _), (p2, _), (p3, _)] = Long::NTT_PRIMES;
+        let p1_inv = utils::pow(p1 as i64, p2 - 2, p2 as i64) as u64;
+        let p1p2_inv = utils::pow((p1 * p2 % p3) as i64, p3 - 2, p3 as i64) as u64;
+        let mut result = Vec::with_capacity(len);
+        let mut carry = 0u128;
+        for ((&r1, &r2), &r3) in residues[0]
+            .iter()
+            .zip(residues[1].iter())
+            .zip(residues[2].iter())
+            .take(len)
+        {
+            let x12 = r1 + p1 * ((r2 + p2 - r1 % p2) % p2 * p1_inv % p2);
+            let t3 = (r3 + p3 - x12 % p3) % p3 * p1p2_inv % p3;
+            let coefficient = x12 as u128 + (p1 * p2) as u128 * t3 as u128 + carry;
+            result.push((coefficient % 1_000_000_000) as u32);
+            carry = coefficient / 1_000_000_000;
+        }
+        result
+    }
+
+    /// Multiply two sequences of digits using the number-theoretic transform.
+    ///
+    /// * `a`
+    /// * `b`
+    fn ntt_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+        if a.is_empty() || b.is_empty() {
+            return vec![];
+        }
+        let len = (a.len() + b.len()).next_power_of_two();
+        if len > Long::NTT_MAX_LEN {
+            panic!("operands are too large for the number-theoretic transform");
+        }
+        let residues = Long::NTT_PRIMES.map(|(prime, generator)| {
+            let mut fa = a.iter().map(|&digit| digit as u64 % prime).collect::<Vec<u64>>();
+            let mut fb = b.iter().map(|&digit| digit as u64 % prime).collect::<Vec<u64>>();
+            fa.resize(len, 0);
+            fb.resize(len, 0);
+            Long::ntt(&mut fa, false, prime, generator);
+            Long::ntt(&mut fb, false, prime, generator);
+            for (x, y) in fa.iter_mut().zip(fb.iter()) {
+                *x = *x * y % prime;
+            }
+            Long::ntt(&mut fa, true, prime, generator);
+            fa
+        });
+        Long::ntt_reconstruct(&residues, a.len() + b.len())
+    }
+
+    /// Square a sequence of digits using the number-theoretic transform. Only
+    /// one forward transform is needed per prime.
+    ///
+    /// * `a`
+    fn ntt_sqr(a: &[u32]) -> Vec<u32> {
+        let len = (2 * a.len()).next_power_of_two();
+        let residues = Long::NTT_PRIMES.map(|(prime, generator)| {
+            let mut fa = a.iter().map(|&digit| digit as u64 % prime).collect::<Vec<u64>>();
+            fa.resize(len, 0);
+            Long::ntt(&mut fa, false, prime, generator);
+            for x in fa.iter_mut() {
+                *x = *x * *x % prime;
+            }
+            Long::ntt(&mut fa, true, prime, generator);
+            fa
+        });
+        Long::ntt_reconstruct(&residues, 2 * a.len())
+    }
+}
+
+impl Long {
+    /// Check whether this number is prime using the Baillie-PSW test: trial
+    /// division, a strong probable prime test to base 2 and a strong Lucas
+    /// probable prime test. No composite number passing this test is known.
+    /// Numbers which fit in a signed 64-bit integer are checked using
+    /// `utils::is_prime` instead, which is deterministic.
+    pub fn is_probable_prime(&self) -> bool {
+        if let Ok(num) = i64::try_from(self) {
+            return utils::is_prime(num);
+        }
+        if [2, 3, 5]
+            .into_iter()
+            .chain(utils::PotentialPrimes::new(1000))
+            .any(|prime| self.div_rem_u32(prime as u32).1 == 0)
+        {
+            return false;
+        }
+        self.is_strong_probable_prime(2) && self.is_strong_lucas_probable_prime()
+    }
+
+    /// Check whether this number is a strong probable prime to the given
+    /// base. This is one round of the Miller-Rabin test.
+    ///
+    /// * `base`
+    fn is_strong_probable_prime(&self, base: u32) -> bool {
+        let num_minus_1 = self - 1;
+        let twopower = num_minus_1.trailing_zeros();
+        let multiplier = &num_minus_1 >> twopower;
+        let mut residue = Long::from(base).pow_mod(&multiplier, self);
+        if residue == Long::one() || residue == num_minus_1 {
+            return true;
+        }
+        for _ in 1..twopower {
+            residue = &residue.square() % self;
+            if residue == num_minus_1 {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Check whether this number is a strong Lucas probable prime, choosing
+    /// the parameters of the Lucas sequences using Selfridge's method.
+    fn is_strong_lucas_probable_prime(&self) -> bool {
+        // There is no suitable discriminant for perfect squares.
+        if self.isqrt().square() == *self {
+            return false;
+        }
+        let discriminant = (5..)
+            .step_by(2)
+            .map(|d: i64| if d % 4 == 1 { d } else { -d })
+            .find(|&d| self.jacobi(d) == -1)
+            .unwrap();
+        let (p, q) = (Long::one(), self.residue((1 - discriminant) / 4));
+        let discriminant = self.residue(discriminant);
+
+        // Calculate the terms of the Lucas sequences at the odd part of one
+        // more than this number, by scanning the bits of the index from the
+        // most significant one.
+        let num_plus_1 = self + &Long::one();
+        let twopower = num_plus_1.trailing_zeros();
+        let multiplier = &num_plus_1 >> twopower;
+        let (mut u, mut v, mut qk) = (Long::one(), p.clone(), q.clone());
+        for bit in multiplier.bits().collect::<Vec<i64>>().into_iter().rev().skip(1) {
+            u = &(&u * &v) % self;
+            v = self.sub_mod(&(&v.square() % self), &(&(&qk * 2) % self));
+            qk = &qk.square() % self;
+            if bit == 1 {
+                (u, v) = (
+                    self.half_mod(&(&(&(&p * &u) + &v) % self)),
+                    self.half_mod(&(&(&(&discriminant * &u) + &(&p * &v)) % self)),
+                );
+                qk = &(&qk * &q) % self;
+            }
+        }
+        if u.is_zero() || v.is_zero() {
+            return true;
+        }
+        for _ in 1..twopower {
+            v = self.sub_mod(&(&v.square() % self), &(&(&qk * 2) % self));
+            if v.is_zero() {
+                return true;
+            }
+            qk = &qk.square() % self;
+        }
+        false
+    }
+
+    /// Count the number of trailing zeros in the binary representation of
+    /// this number.
+    fn trailing_zeros(&self) -> u32 {
+        let digits = self.binary_digits();
+        let zeros = digits.iter().position(|&digit| digit != 0).unwrap_or(0);
+        zeros as u32 * 32 + digits.get(zeros).map_or(0, |digit| digit.trailing_zeros())
+    }
+
+    /// Calculate the residue of a signed number modulo this number.
+    ///
+    /// * `num`
+    fn residue(&self, num: i64) -> Long {
+        let rem = &Long::from(num.unsigned_abs()) % self;
+        if num >= 0 || rem.is_zero() {
+            rem
+        } else {
+            self - &rem
+        }
+    }
+
+    /// Subtract two residues modulo this number.
+    ///
+    /// * `a`
+    /// * `b`
+    fn sub_mod(&self, a: &Long, b: &Long) -> Long {
+        match a.checked_sub(b) {
+            Some(difference) => difference,
+            None => &(a + self) - b,
+        }
+    }
+
+    /// Halve a residue modulo this number.
+    ///
+    /// * `num` Residue. This number must be odd.
+    fn half_mod(&self, num: &Long) -> Long {
+        if num.digits[0].is_multiple_of(2) {
+            num / 2
+        } else {
+            &(num + self) / 2
+        }
+    }
+
+    /// Calculate the Jacobi symbol of the given number with respect to this
+    /// number.
+    ///
+    /// * `num`
+    ///
+    /// Returns -1, 0 or 1. This number must be odd.
+    fn jacobi(&self, num: i64) -> i64 {
+        let mod_8 = self.div_rem_u32(8).1;
+        let mut result = if num < 0 && mod_8 % 4 == 3 { -1 } else { 1 };
+        let mut num = num.unsigned_abs();
+        if num == 0 {
+            return 0;
+        }
+        let twopower = num.trailing_zeros();
+        num >>= twopower;
+        if twopower % 2 == 1 && (mod_8 == 3 || mod_8 == 5) {
+            result = -result;
+        }
+
+        // Use quadratic reciprocity to swap the arguments, after which both
+        // are small.
+        if num % 4 == 3 && mod_8 % 4 == 3 {
+            result = -result;
+        }
+        let (mut a, mut n) = (self.div_rem_u32(num as u32).1 as u64, num);
+        while a != 0 {
+            while a % 2 == 0 {
+                a /= 2;
+                if n % 8 == 3 || n % 8 == 5 {
+                    result = -result;
+                }
+            }
+            (a, n) = (n, a);
+            if a % 4 == 3 && n % 4 == 3 {
+                result = -result;
+            }
+            a %= n;
+        }
+        if n == 1 {
+            result
+        } else {
+            0
+        }
+    }
+
+    /// Find the smallest prime number greater than this number, as
+    /// determined by `is_probable_prime`.
+    pub fn next_prime(&self) -> Long {
+        if *self < Long::from(2u32) {
+            return Long::from(2u32);
+        }
+        let mut candidate = self + &Long::one();
+        if candidate.digits[0].is_multiple_of(2) {
+            candidate += &Long::one();
+        }
+        while !candidate.is_probable_prime() {
+            candidate += &Long::from(2u32);
+        }
+        candidate
+    }
+
+    /// Factorise this number using trial division followed by Brent's
+    /// variant of Pollard's rho algorithm. The latter finds factors of about
+    /// 20 digits in reasonable time; larger factors may take very long.
+    ///
+    /// Returns the prime factors and their exponents in ascending order of
+    /// the prime factors. This number must be positive.
+    pub fn factorise(&self) -> Vec<(Long, u32)> {
+        if self.is_zero() {
+            panic!("zero cannot be factorised");
+        }
+        let mut factors = vec![];
+        let mut num = self.clone();
+        for prime in [2, 3, 5].into_iter().chain(utils::PotentialPrimes::new(10000)) {
+            // What remains is either 1 or a prime number.
+            if num.digits.len() == 1 && (num.digits[0] as i64) < prime * prime {
+                break;
+            }
+            loop {
+                let (quotient, rem) = num.div_rem_u32(prime as u32);
+                if rem != 0 {
+                    break;
+                }
+                factors.push(Long::from(prime as u32));
+                num = quotient;
+            }
+        }
+        let mut composites = vec![num];
+        while let Some(num) = composites.pop() {
+            if num == Long::one() {
+                continue;
+            }
+            if num.is_probable_prime() {
+                factors.push(num);
+                continue;
+            }
+            let factor = num.pollard_rho();
+            composites.push(&num / &factor);
+            composites.push(factor);
+        }
+        factors.sort();
+        let mut result: Vec<(Long, u32)> = vec![];
+        for factor in factors {
+            match result.last_mut() {
+                Some((prime, exponent)) if *prime == factor => *exponent += 1,
+                _ => result.push((factor, 1)),
+            }
+        }
+        result
+    }
+
+    /// Find a non-trivial factor of this number using Brent's variant of
+    /// Pollard's rho algorithm.
+    ///
+    /// Returns a factor greater than 1 and less than this number. This number
+    /// must be composite and have no prime factors less than 7.
+    fn pollard_rho(&self) -> Long {
+        let distance = |a: &Long, b: &Long| if a > b { a - b } else { b - a };
+        for increment in 1u32.. {
+            let increment = Long::from(increment);
+            let next = |x: &Long| &(&x.square() + &increment) % self;
+            let (mut x, mut y, mut ys) = (Long::from(2u32), Long::from(2u32), Long::from(2u32));
+            let (mut product, mut factor) = (Long::one(), Long::one());
+            let mut steps = 1;
+            while factor == Long::one() {
+                x = y.clone();
+                for _ in 0..steps {
+                    y = next(&y);
+                }
+
+                // Accumulate differences in batches to reduce the number of
+                // greatest common divisor calculations.
+                let mut taken = 0;
+                while taken < steps && factor == Long::one() {
+                    ys = y.clone();
+                    for _ in 0..std::cmp::min(128, steps - taken) {
+                        y = next(&y);
+                        product = &(&product * &distance(&x, &y)) % self;
+                    }
+                    factor = product.gcd(self);
+                    taken += 128;
+                }
+                steps *= 2;
+            }
+
+            // If a batch overshot, step through it one at a time.
+            if factor == *self {
+                loop {
+                    ys = next(&ys);
+                    factor = distance(&x, &ys).gcd(self);
+                    if factor != Long::one() {
+                        break;
+                    }
+                }
+            }
+            if factor != *self {
+                return factor;
+            }
+        }
+        unreachable!();
+    }
+}
+
+/// Error returned when a string cannot be parsed as an arbitrary-precision
+/// integer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseLongError {
+    /// The string has no digits.
+    Empty,
+    /// The string contains a character which is not a decimal digit.
+    InvalidDigit,
+    /// The string represents a negative number, but the type is unsigned.
+    Negative,
+}
+
+impl std::fmt::Display for ParseLongError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseLongError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseLongError::InvalidDigit => write!(f, "invalid digit found in string"),
+            ParseLongError::Negative => write!(f, "negative number cannot be parsed as an unsigned integer"),
+        }
+    }
+}
+
+impl std::error::Error for ParseLongError {}
+
+/// Error returned when an arbitrary-precision integer does not fit in a
+/// primitive integer type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TryFromLongError;
+
+impl std::fmt::Display for TryFromLongError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "out of range integral type conversion attempted")
+    }
+}
+
+impl std::error::Error for TryFromLongError {}
+
+impl Ord for Long {
+    fn cmp(&self, other: &Long) -> std::cmp::Ordering {
+        // Numbers are normalised, so the one with more digits is larger.
+        // Otherwise, compare digits starting from the most significant one.
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl PartialOrd for Long {
+    fn partial_cmp(&self, other: &Long) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Default for Long {
+    fn default() -> Long {
+        Long::zero()
+    }
+}
+
+impl std::iter::Sum for Long {
+    fn sum<I: Iterator<Item = Long>>(iter: I) -> Long {
+        iter.fold(Long::zero(), |mut result, item| {
+            result += &item;
+            result
+        })
+    }
+}
+
+impl<'a> std::iter::Sum<&'a Long> for Long {
+    fn sum<I: Iterator<Item = &'a Long>>(iter: I) -> Long {
+        iter.fold(Long::zero(), |mut result, item| {
+            result += item;
+            result
+        })
+    }
+}
+
+impl std::iter::Product for Long {
+    fn product<I: Iterator<Item = Long>>(iter: I) -> Long {
+        iter.fold(Long::one(), |result, item| &result * &item)
+    }
+}
+
+impl<'a> std::iter::Product<&'a Long> for Long {
+    fn product<I: Iterator<Item = &'a Long>>(iter: I) -> Long {
+        iter.fold(Long::one(), |result, item| &result * item)
+    }
+}
+
+impl std::str::FromStr for Long {
+    type Err = ParseLongError;
+    fn from_str(s: &str) -> Result<Long, ParseLongError> {
+        if s.starts_with('-') {
+            return Err(ParseLongError::Negative);
+        }
+        let s = s.strip_prefix('+').unwrap_or(s);
+        if s.is_empty() {
+            return Err(ParseLongError::Empty);
+        }
+        if !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseLongError::InvalidDigit);
+        }
+        let mut long = Long { digits: vec![] };
+        let mut idx = s.len();
+        loop {
+            let (lower, upper) = (std::cmp::max(idx, 9) - 9, idx);
+            long.digits.push(s[lower..upper].parse().unwrap());
+            if lower == 0 {
+                break;
+            }
+            idx = lower;
+        }
+        long.normalise();
+        Ok(long)
+    }
+}
+
+impl From<u128> for Long {
+    fn from(mut num: u128) -> Long {
+        let mut long = Long { digits: vec![] };
+        loop {
+            long.digits.push((num % 1_000_000_000) as u32);
+            num /= 1_000_000_000;
+            if num == 0 {
+                return long;
+            }
+        }
+    }
+}
+
+impl From<u64> for Long {
+    fn from(num: u64) -> Long {
+        Long::from(num as u128)
+    }
+}
+
+impl From<u32> for Long {
+    fn from(num: u32) -> Long {
+        Long::from(num as u128)
+    }
+}
+
+impl From<usize> for Long {
+    fn from(num: usize) -> Long {
+        Long::from(num as u128)
+    }
+}
+
+impl TryFrom<&Long> for u128 {
+    type Error = TryFromLongError;
+    fn try_from(long: &Long) -> Result<u128, TryFromLongError> {
+        long.digits.iter().rev().try_fold(0u128, |result, &digit| {
+            result
+                .checked_mul(1_000_000_000)
+                .and_then(|result| result.checked_add(digit as u128))
+                .ok_or(TryFromLongError)
+        })
+    }
+}
+
+impl TryFrom<&Long> for u64 {
+    type Error = TryFromLongError;
+    fn try_from(long: &Long) -> Result<u64, TryFromLongError> {
+        u128::try_from(long)?.try_into().map_err(|_| TryFromLongError)
+    }
+}
+
+impl TryFrom<&Long> for i64 {
+    type Error = TryFromLongError;
+    fn try_from(long: &Long) -> Result<i64, TryFromLongError> {
+        u128::try_from(long)?.try_into().map_err(|_| TryFromLongError)
+    }
+}
+
+impl std::ops::AddAssign<&Long> for Long {
+    fn add_assign(&mut self, other: &Long) {
+        self.digits
+            .resize(std::cmp::max(self.digits.len(), other.digits.len()), 0);
+        let mut carry = false;
+        for (sd, od) in self
+            .digits
+            .iter_mut()
+            .zip(other.digits.iter().chain(std::iter::repeat(&0)))
+        {
+            (*sd, carry) = Long::adc(*sd, *od, carry);
+        }
+        if carry {
+            self.digits.push(1);
+        }
+    }
+}
+
+impl std::ops::Add<&Long> for &Long {
+    type Output = Long;
+    fn add(self, other: &Long) -> Long {
+        let mut result = self.clone();
+        result += other;
+        result
+    }
+}
+
+impl std::ops::SubAssign<&Long> for Long {
+    fn sub_assign(&mut self, other: &Long) {
+        self.sub_digits(&other.digits);
+    }
+}
+
+impl std::ops::SubAssign<u32> for Long {
+    fn sub_assign(&mut self, other: u32) {
+        self.sub_digits(&Long::split(other));
+    }
+}
+
+impl std::ops::Sub<&Long> for &Long {
+    type Output = Long;
+    fn sub(self, other: &Long) -> Long {
+        let mut result = self.clone();
+        result -= other;
+        result
+    }
+}
+
+impl std::ops::Sub<u32> for &Long {
+    type Output = Long;
+    fn sub(self, other: u32) -> Long {
+        let mut result = self.clone();
+        result -= other;
+        result
+    }
+}
+
+impl std::ops::MulAssign<u32> for Long {
+    fn mul_assign(&mut self, other: u32) {
+        let mut carry = 0;
+        for sd in self.digits.iter_mut() {
+            (*sd, carry) = Long::mlc(*sd, other, carry);
+        }
+        while carry > 0 {
+            self.digits.push(carry % 1_000_000_000);
+            carry /= 1_000_000_000;
+        }
+        self.normalise();
+    }
+}
+
+impl std::ops::Mul<u32> for &Long {
+    type Output = Long;
+    fn mul(self, other: u32) -> Long {
+        let mut result = self.clone();
+        result *= other;
+        result
+    }
+}
+
+impl std::ops::Mul<&Long> for &Long {
+    type Output = Long;
+    fn mul(self, other: &Long) -> Long {
+        let mut result = Long {
+            digits: Long::mul_digits(&self.digits, &other.digits, true),
+        };
+        result.normalise();
+        result
+    }
+}
+
+impl std::ops::DivAssign<&Long> for Long {
+    fn div_assign(&mut self, other: &Long) {
+        *self = self.div_rem(other).0;
+    }
+}
+
+impl std::ops::DivAssign<u32> for Long {
+    fn div_assign(&mut self, other: u32) {
+        *self = self.div_rem_u32(other).0;
+    }
+}
+
+impl std::ops::Div<&Long> for &Long {
+    type Output = Long;
+    fn div(self, other: &Long) -> Long {
+        self.div_rem(other).0
+    }
+}
+
+impl std::ops::Div<u32> for &Long {
+    type Output = Long;
+    fn div(self, other: u32) -> Long {
+        self.div_rem_u32(other).0
+    }
+}
+
+impl std::ops::RemAssign<&Long> for Long {
+    fn rem_assign(&mut self, other: &Long) {
+        *self = self.div_rem(other).1;
+    }
+}
+
+impl std::ops::RemAssign<u32> for Long {
+    fn rem_assign(&mut self, other: u32) {
+        *self = &*self % other;
+    }
+}
+
+impl std::ops::Rem<&Long> for &Long {
+    type Output = Long;
+    fn rem(self, other: &Long) -> Long {
+        self.div_rem(other).1
+    }
+}
+
+impl std::ops::Rem<u32> for &Long {
+    type Output = Long;
+    fn rem(self, other: u32) -> Long {
+        let rem = self.div_rem_u32(other).1;
+        let mut result = Long {
+            digits: Long::split(rem).to_vec(),
+        };
+        result.normalise();
+        result
+    }
+}
+
+impl std::ops::ShlAssign<u32> for Long {
+    fn shl_assign(&mut self, other: u32) {
+        *self = &*self << other;
+    }
+}
+
+impl std::ops::Shl<u32> for &Long {
+    type Output = Long;
+    fn shl(self, other: u32) -> Long {
+        let mut result = Long {
+            digits: Long::mul_digits(&self.digits, &Long::from(2u32).pow(other).digits, true),
+        };
+        result.normalise();
+        result
+    }
+}
+
+impl std::ops::ShrAssign<u32> for Long {
+    fn shr_assign(&mut self, other: u32) {
+        *self = &*self >> other;
+    }
+}
+
+impl std::ops::Shr<u32> for &Long {
+    type Output = Long;
+    fn shr(self, other: u32) -> Long {
+        self.div_rem(&Long::from(2u32).pow(other)).0
+    }
+}
+
+impl std::ops::BitAnd<&Long> for &Long {
+    type Output = Long;
+    fn bitand(self, other: &Long) -> Long {
+        self.bitwise(other, |s, o| s & o)
+    }
+}
+
+impl std::ops::BitOr<&Long> for &Long {
+    type Output = Long;
+    fn bitor(self, other: &Long) -> Long {
+        self.bitwise(other, |s, o| s | o)
+    }
+}
+
+impl std::ops::BitXor<&Long> for &Long {
+    type Output = Long;
+    fn bitxor(self, other: &Long) -> Long {
+        self.bitwise(other, |s, o| s ^ o)
+    }
 }
 
 impl std::fmt::Display for Long {
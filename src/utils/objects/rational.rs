@@ -0,0 +1,344 @@
+use crate::utils;
+
+/// Rational numbers stored as a pair of machine-word integers. The
+/// denominator is always positive, and the numerator and denominator are
+/// always coprime. Hence, two equal rational numbers have the same
+/// representation. Intermediate results are calculated using 128-bit
+/// integers, so that an operation fails only if its result cannot be
+/// represented.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Rational {
+    /// Construct a rational number.
+    ///
+    /// * `numerator`
+    /// * `denominator` Must be non-zero.
+    ///
+    /// Returns a rational number which is the ratio of the given numbers,
+    /// reduced to its lowest form. Panics if it cannot be represented (which
+    /// happens only if the value is `i64::MIN` negated).
+    pub fn new(numerator: i64, denominator: i64) -> Rational {
+        if denominator == 0 {
+            panic!("denominator of a rational number cannot be zero");
+        }
+        Rational::checked_from(numerator as i128, denominator as i128).expect("overflow detected")
+    }
+
+    /// Construct a rational number from a pair of 128-bit integers.
+    ///
+    /// * `numerator`
+    /// * `denominator` Must be non-zero.
+    ///
+    /// Returns the reduced rational number if it can be represented.
+    fn checked_from(numerator: i128, denominator: i128) -> Option<Rational> {
+        let gcd = Rational::gcd(numerator.unsigned_abs(), denominator.unsigned_abs()) as i128;
+        let (numerator, denominator) = if denominator < 0 {
+            (-numerator / gcd, -denominator / gcd)
+        } else {
+            (numerator / gcd, denominator / gcd)
+        };
+        Some(Rational {
+            numerator: numerator.try_into().ok()?,
+            denominator: denominator.try_into().ok()?,
+        })
+    }
+
+    /// Calculate the greatest common divisor of two numbers.
+    ///
+    /// * `a`
+    /// * `b`
+    fn gcd(mut a: u128, mut b: u128) -> u128 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// Obtain the numerator. Its sign is the sign of the rational number.
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    /// Obtain the denominator. It is always positive.
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+
+    /// Add another rational number to this one.
+    ///
+    /// * `other`
+    ///
+    /// Returns the sum if it can be represented.
+    pub fn checked_add(&self, other: &Rational) -> Option<Rational> {
+        Rational::checked_from(
+            self.numerator as i128 * other.denominator as i128 + other.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
+    }
+
+    /// Subtract another rational number from this one.
+    ///
+    /// * `other`
+    ///
+    /// Returns the difference if it can be represented.
+    pub fn checked_sub(&self, other: &Rational) -> Option<Rational> {
+        Rational::checked_from(
+            self.numerator as i128 * other.denominator as i128 - other.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
+    }
+
+    /// Multiply this rational number by another one.
+    ///
+    /// * `other`
+    ///
+    /// Returns the product if it can be represented.
+    pub fn checked_mul(&self, other: &Rational) -> Option<Rational> {
+        Rational::checked_from(
+            self.numerator as i128 * other.numerator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
+    }
+
+    /// Divide this rational number by another one.
+    ///
+    /// * `other`
+    ///
+    /// Returns the quotient if the divisor is non-zero and the quotient can
+    /// be represented.
+    pub fn checked_div(&self, other: &Rational) -> Option<Rational> {
+        if other.numerator == 0 {
+            return None;
+        }
+        Rational::checked_from(
+            self.numerator as i128 * other.denominator as i128,
+            self.denominator as i128 * other.numerator as i128,
+        )
+    }
+
+    /// Negate this rational number.
+    ///
+    /// Returns the negation if it can be represented.
+    pub fn checked_neg(&self) -> Option<Rational> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+
+    /// Calculate the reciprocal of this rational number.
+    ///
+    /// Returns the reciprocal if this number is non-zero and the reciprocal
+    /// can be represented.
+    pub fn checked_recip(&self) -> Option<Rational> {
+        if self.numerator == 0 {
+            return None;
+        }
+        Rational::checked_from(self.denominator as i128, self.numerator as i128)
+    }
+
+    /// Calculate the mediant of this rational number and another one. This is
+    /// the rational number whose numerator and denominator are the sums of
+    /// the respective numerators and denominators. It lies between the two
+    /// numbers.
+    ///
+    /// * `other`
+    ///
+    /// Returns the mediant if it can be represented.
+    pub fn mediant(&self, other: &Rational) -> Option<Rational> {
+        Rational::checked_from(
+            self.numerator as i128 + other.numerator as i128,
+            self.denominator as i128 + other.denominator as i128,
+        )
+    }
+
+    /// Calculate the largest integer less than or equal to this rational
+    /// number.
+    pub fn floor(&self) -> i64 {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    /// Calculate the smallest integer greater than or equal to this rational
+    /// number.
+    pub fn ceil(&self) -> i64 {
+        -(-(self.numerator as i128)).div_euclid(self.denominator as i128) as i64
+    }
+
+    /// Calculate the integer nearest to this rational number, rounding
+    /// half-way cases away from zero.
+    pub fn round(&self) -> i64 {
+        let (numerator, denominator) = (self.numerator as i128, self.denominator as i128);
+        let rounded = (2 * numerator.abs() + denominator) / (2 * denominator);
+        (rounded * numerator.signum()) as i64
+    }
+
+    /// Find the neighbours of this rational number among the rational
+    /// numbers whose denominators do not exceed a limit. See
+    /// `Fraction::farey_neighbours`.
+    ///
+    /// * `max_denominator` Must be positive.
+    pub fn farey_neighbours(&self, max_denominator: i64) -> (Rational, Rational) {
+        let max_denominator = utils::Long::from(u64::try_from(max_denominator).unwrap());
+        let fraction: utils::Fraction = (*self).into();
+        let (left, right) = fraction.farey_neighbours(&max_denominator);
+        (
+            Rational::try_from(&left).expect("overflow detected"),
+            Rational::try_from(&right).expect("overflow detected"),
+        )
+    }
+
+    /// Find the rational number closest to this one among the rational
+    /// numbers whose denominators do not exceed a limit. See
+    /// `Fraction::best_approximation`.
+    ///
+    /// * `max_denominator` Must be positive.
+    ///
+    /// Returns the best approximation if it can be represented.
+    pub fn best_approximation(&self, max_denominator: i64) -> Result<Rational, utils::TryFromLongError> {
+        let max_denominator = utils::Long::from(u64::try_from(max_denominator).unwrap());
+        let fraction: utils::Fraction = (*self).into();
+        let approximation = fraction.best_approximation(&max_denominator);
+        Rational::try_from(&approximation)
+    }
+
+    /// Find the rational number closest to a real number among the rational
+    /// numbers whose denominators do not exceed a limit. See
+    /// `Fraction::approximate`. Panics if the result cannot be represented.
+    ///
+    /// * `num` Must be finite.
+    /// * `max_denominator` Must be positive.
+    pub fn approximate(num: f64, max_denominator: i64) -> Rational {
+        let max_denominator = utils::Long::from(u64::try_from(max_denominator).unwrap());
+        let approximation = utils::Fraction::approximate(num, &max_denominator);
+        Rational::try_from(&approximation).expect("overflow detected")
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(num: i64) -> Rational {
+        Rational {
+            numerator: num,
+            denominator: 1,
+        }
+    }
+}
+
+impl From<Rational> for utils::Fraction {
+    fn from(rational: Rational) -> utils::Fraction {
+        utils::Fraction::new(
+            utils::BigInt::from(rational.numerator),
+            utils::BigInt::from(rational.denominator),
+        )
+    }
+}
+
+impl TryFrom<&utils::Fraction> for Rational {
+    type Error = utils::TryFromLongError;
+    fn try_from(fraction: &utils::Fraction) -> Result<Rational, utils::TryFromLongError> {
+        // Reduce first, so that the conversion fails only if the value cannot
+        // be represented. The magnitude of the numerator may then be one more
+        // than `i64::MAX` (if the value is `i64::MIN`).
+        let mut fraction = fraction.clone();
+        fraction.reduce();
+        let numerator = u64::try_from(fraction.numerator().magnitude())? as i128;
+        let numerator = if fraction.numerator().is_negative() {
+            -numerator
+        } else {
+            numerator
+        };
+        Rational::checked_from(numerator, u64::try_from(fraction.denominator())? as i128)
+            .ok_or(utils::TryFromLongError)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> std::cmp::Ordering {
+        // Denominators are positive, so cross-multiplying preserves the
+        // order. The products cannot overflow 128 bits.
+        (self.numerator as i128 * other.denominator as i128).cmp(&(other.numerator as i128 * self.denominator as i128))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::ops::Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        self.checked_neg().expect("overflow detected")
+    }
+}
+
+impl std::ops::Add for Rational {
+    type Output = Rational;
+    fn add(self, other: Rational) -> Rational {
+        self.checked_add(&other).expect("overflow detected")
+    }
+}
+
+impl std::ops::AddAssign for Rational {
+    fn add_assign(&mut self, other: Rational) {
+        *self = self.checked_add(&other).expect("overflow detected");
+    }
+}
+
+impl std::ops::Sub for Rational {
+    type Output = Rational;
+    fn sub(self, other: Rational) -> Rational {
+        self.checked_sub(&other).expect("overflow detected")
+    }
+}
+
+impl std::ops::SubAssign for Rational {
+    fn sub_assign(&mut self, other: Rational) {
+        *self = self.checked_sub(&other).expect("overflow detected");
+    }
+}
+
+impl std::ops::Mul for Rational {
+    type Output = Rational;
+    fn mul(self, other: Rational) -> Rational {
+        self.checked_mul(&other).expect("overflow detected")
+    }
+}
+
+impl std::ops::MulAssign for Rational {
+    fn mul_assign(&mut self, other: Rational) {
+        *self = self.checked_mul(&other).expect("overflow detected");
+    }
+}
+
+impl std::ops::Div for Rational {
+    type Output = Rational;
+    fn div(self, other: Rational) -> Rational {
+        if other.numerator == 0 {
+            panic!("division by zero");
+        }
+        self.checked_div(&other).expect("overflow detected")
+    }
+}
+
+impl std::ops::DivAssign for Rational {
+    fn div_assign(&mut self, other: Rational) {
+        *self = *self / other;
+    }
+}
+
+impl std::fmt::Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl std::fmt::Debug for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
@@ -7,16 +7,24 @@ use crate::utils;
 /// Eratosthenes implementation (which I have now removed). It only determines
 /// the primality of numbers coprime to 60, because other numbers are
 /// guaranteed to be composite. (Exceptions 2, 3 and 5 are handled separately.)
+#[derive(Eq, PartialEq)]
 pub struct SieveOfAtkin {
     limit: usize,
     limit_rounded: usize,
     limit_rounded_isqrt: usize,
     sieve: Vec<u16>,
+    // Whether to use a primality test for numbers out of range of the sieve
+    // instead of panicking.
+    fallback: bool,
+    // Number of prime numbers (other than 2, 3 and 5) in the bitfields
+    // preceding each block of bitfields.
+    ranks: Vec<usize>,
 }
 
 impl SieveOfAtkin {
-    // Consecutive differences between coprime residues modulo 60: 1, 7, 11,
-    // 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 49, 53 and 59.
+    // Coprime residues modulo 60.
+    const RESIDUES: [usize; 16] = [1, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 49, 53, 59];
+    // Consecutive differences between coprime residues modulo 60.
     const OFFSETS: [usize; 16] = [6, 4, 2, 4, 2, 4, 6, 2, 6, 4, 2, 4, 2, 4, 6, 2];
     // Position of the bit indicating the primality of a coprime residue modulo
     // 60 in a 16-element bitfield. For non-coprime residues, the value is 16.
@@ -25,6 +33,15 @@ impl SieveOfAtkin {
         7, 16, 8, 16, 16, 16, 16, 16, 9, 16, 16, 16, 10, 16, 11, 16, 16, 16, 12, 16, 13, 16, 16, 16, 14, 16, 16, 16,
         16, 16, 15,
     ];
+    // Number of bitfields per block for which the number of prime numbers
+    // preceding it is stored.
+    const RANK_BLOCK_LEN: usize = 32;
+    // Identification of the on-disk format, and the size of its header. The
+    // header contains the magic bytes, the version, the limit and the
+    // checksum; the bitfields follow it.
+    const MAGIC: [u8; 4] = *b"SOA\0";
+    const VERSION: u32 = 1;
+    const HEADER_LEN: usize = 24;
 }
 
 impl SieveOfAtkin {
@@ -32,34 +49,131 @@ impl SieveOfAtkin {
     ///
     /// * `limit` Non-strict upper bound.
     pub fn new(limit: usize) -> SieveOfAtkin {
-        // Strict upper bound divisible by 60.
-        let limit_rounded = (limit - limit % 60)
-            .checked_add(60)
-            .expect("overflow detected; argument too large");
-        let mut sieve_of_atkin = SieveOfAtkin {
+        SieveOfAtkin::try_new(limit).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Construct the sieve of Atkin up to and including the given number.
+    ///
+    /// * `limit` Non-strict upper bound.
+    ///
+    /// Returns an error instead of panicking if the limit is too large or the
+    /// memory for the sieve cannot be allocated.
+    pub fn try_new(limit: usize) -> Result<SieveOfAtkin, SieveError> {
+        let mut sieve_of_atkin = SieveOfAtkin::allocate(limit)?;
+        sieve_of_atkin.init();
+        sieve_of_atkin.init_ranks();
+        Ok(sieve_of_atkin)
+    }
+
+    /// Allocate an uninitialised sieve of Atkin.
+    ///
+    /// * `limit` Non-strict upper bound.
+    fn allocate(limit: usize) -> Result<SieveOfAtkin, SieveError> {
+        let limit_rounded = SieveOfAtkin::round(limit)?;
+        let mut sieve = vec![];
+        sieve
+            .try_reserve_exact(limit / 60 + 1)
+            .map_err(|_| SieveError::Allocation)?;
+        sieve.resize(limit / 60 + 1, 0);
+        Ok(SieveOfAtkin {
             limit,
             limit_rounded,
             limit_rounded_isqrt: utils::isqrt(limit_rounded as i64) as usize,
-            sieve: vec![0; limit / 60 + 1],
-        };
-        sieve_of_atkin.init();
-        sieve_of_atkin
+            sieve,
+            fallback: false,
+            ranks: vec![],
+        })
+    }
+
+    /// Calculate the strict upper bound divisible by 60 corresponding to a
+    /// limit. It must be representable as a signed integer, because the
+    /// generated prime numbers are.
+    ///
+    /// * `limit` Non-strict upper bound.
+    fn round(limit: usize) -> Result<usize, SieveError> {
+        (limit - limit % 60)
+            .checked_add(60)
+            .filter(|&limit_rounded| i64::try_from(limit_rounded).is_ok())
+            .ok_or(SieveError::Overflow)
+    }
+
+    /// Serialise the sieve of Atkin. The result contains a header (magic
+    /// bytes, format version, limit and FNV-1a checksum) followed by the
+    /// bitfields, all in little-endian byte order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SieveOfAtkin::HEADER_LEN + 2 * self.sieve.len());
+        bytes.extend(SieveOfAtkin::MAGIC);
+        bytes.extend(SieveOfAtkin::VERSION.to_le_bytes());
+        bytes.extend((self.limit as u64).to_le_bytes());
+        bytes.extend([0; 8]);
+        bytes.extend(self.sieve.iter().flat_map(|bitfield| bitfield.to_le_bytes()));
+        let checksum = SieveOfAtkin::checksum(&bytes);
+        bytes[16..24].copy_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Deserialise a sieve of Atkin serialised by `to_bytes`.
+    ///
+    /// * `bytes`
+    ///
+    /// Returns an error if the bytes are not a valid serialisation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SieveOfAtkin, SieveError> {
+        let view = SieveView::new(bytes)?;
+        let mut sieve_of_atkin = SieveOfAtkin::allocate(view.limit)?;
+        for (bitfield, chunk) in sieve_of_atkin.sieve.iter_mut().zip(view.bitfields.chunks_exact(2)) {
+            *bitfield = u16::from_le_bytes([chunk[0], chunk[1]]);
+        }
+        sieve_of_atkin.init_ranks();
+        Ok(sieve_of_atkin)
+    }
+
+    /// Write the sieve of Atkin to a file. See `to_bytes`.
+    ///
+    /// * `path`
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), SieveError> {
+        std::fs::write(path, self.to_bytes()).map_err(|err| SieveError::Io(err.kind()))
+    }
+
+    /// Read a sieve of Atkin from a file written by `save`.
+    ///
+    /// * `path`
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<SieveOfAtkin, SieveError> {
+        let bytes = std::fs::read(path).map_err(|err| SieveError::Io(err.kind()))?;
+        SieveOfAtkin::from_bytes(&bytes)
+    }
+
+    /// Calculate the 64-bit FNV-1a hash of a serialised sieve, treating the
+    /// bytes of the checksum field as zeros.
+    ///
+    /// * `bytes` Must be at least as long as the header.
+    fn checksum(bytes: &[u8]) -> u64 {
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(idx, &byte)| if (16..24).contains(&idx) { 0 } else { byte })
+            .fold(0xcbf29ce484222325, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            })
+    }
+
+    /// Choose what happens when the primality of a number out of range of
+    /// the sieve is queried.
+    ///
+    /// * `fallback` If `true`, the primality is determined using `is_prime`
+    ///   in the `utils` module. If `false` (the default), the query panics.
+    pub fn with_fallback(mut self, fallback: bool) -> SieveOfAtkin {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Obtain the number the sieve was constructed up to.
+    pub fn limit(&self) -> usize {
+        self.limit
     }
 
     /// Initialise the sieve of Atkin.
     fn init(&mut self) {
-        for (delta, shift) in [1, 13, 17, 29, 37, 41, 49, 53]
-            .into_iter()
-            .zip([0, 3, 4, 7, 9, 10, 13, 14])
-        {
-            self.algorithm_3_1(delta, shift);
-        }
-        for (delta, shift) in [7, 19, 31, 43].into_iter().zip([1, 5, 8, 11]) {
-            self.algorithm_3_2(delta, shift);
-        }
-        for (delta, shift) in [11, 23, 47, 59].into_iter().zip([2, 6, 12, 15]) {
-            self.algorithm_3_3(delta, shift);
-        }
+        SieveOfAtkin::init_range(&mut self.sieve, 0);
 
         // Mark composite all numbers divisible by the squares of primes.
         let mut num: usize = 1;
@@ -83,17 +197,122 @@ impl SieveOfAtkin {
         }
     }
 
+    /// Construct the sieve of Atkin up to and including the given number,
+    /// using multiple threads. The result is identical to that of `new`.
+    ///
+    /// * `limit` Non-strict upper bound.
+    /// * `threads` Number of threads to use. If `None`, the available
+    ///   parallelism is used.
+    pub fn new_parallel(limit: usize, threads: Option<usize>) -> SieveOfAtkin {
+        SieveOfAtkin::try_new_parallel(limit, threads).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Construct the sieve of Atkin up to and including the given number,
+    /// using multiple threads.
+    ///
+    /// * `limit` Non-strict upper bound.
+    /// * `threads` Number of threads to use. If `None`, the available
+    ///   parallelism is used.
+    ///
+    /// Returns an error instead of panicking if the limit is too large or the
+    /// memory for the sieve cannot be allocated.
+    pub fn try_new_parallel(limit: usize, threads: Option<usize>) -> Result<SieveOfAtkin, SieveError> {
+        let mut sieve_of_atkin = SieveOfAtkin::allocate(limit)?;
+        let threads = threads
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |threads| threads.get()))
+            .clamp(1, sieve_of_atkin.sieve.len());
+        sieve_of_atkin.init_parallel(threads);
+        sieve_of_atkin.init_ranks();
+        Ok(sieve_of_atkin)
+    }
+
+    /// Initialise the sieve of Atkin using multiple threads. Each thread
+    /// works on a separate range of bitfields.
+    ///
+    /// * `threads` Number of threads to use.
+    fn init_parallel(&mut self, threads: usize) {
+        // The numbers whose squares must be crossed out are the prime numbers
+        // up to the square root of the upper bound. Obtain them from a smaller
+        // sieve, so that the threads need not wait for one another.
+        let base_primes = SieveOfAtkin::new(self.limit_rounded_isqrt)
+            .iter()
+            .skip(3)
+            .map(|prime| prime as usize)
+            .collect::<Vec<usize>>();
+        let chunk_len = self.sieve.len().div_ceil(threads);
+        let limit_rounded = self.limit_rounded;
+        std::thread::scope(|scope| {
+            for (chunk_idx, chunk) in self.sieve.chunks_mut(chunk_len).enumerate() {
+                let base_primes = &base_primes;
+                scope.spawn(move || {
+                    let lo = chunk_idx * chunk_len;
+                    SieveOfAtkin::init_range(chunk, lo);
+                    let (lo_num, hi_num) = (lo * 60, ((lo + chunk.len()) * 60).min(limit_rounded));
+                    for &prime in base_primes.iter().take_while(|&&prime| prime * prime < hi_num) {
+                        let prime_sqr = prime * prime;
+                        for multiple in (lo_num.div_ceil(prime_sqr).max(1) * prime_sqr..hi_num).step_by(prime_sqr) {
+                            chunk[multiple / 60 - lo] &= !(1u32 << SieveOfAtkin::SHIFTS[multiple % 60]) as u16;
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    /// Count the prime numbers preceding each block of bitfields.
+    fn init_ranks(&mut self) {
+        let mut rank = 0;
+        self.ranks = self
+            .sieve
+            .chunks(SieveOfAtkin::RANK_BLOCK_LEN)
+            .map(|block| {
+                let preceding = rank;
+                rank += block
+                    .iter()
+                    .map(|bitfield| bitfield.count_ones() as usize)
+                    .sum::<usize>();
+                preceding
+            })
+            .collect();
+    }
+
+    /// Find the numbers which have an odd number of representations by the
+    /// binary quadratic forms applicable to their residues modulo 60. This
+    /// includes all prime numbers.
+    ///
+    /// * `sieve` Bitfields to update.
+    /// * `lo` Index of the first bitfield, were it part of a sieve starting
+    ///   from 0.
+    fn init_range(sieve: &mut [u16], lo: usize) {
+        let (lo, hi) = (lo as i64, (lo + sieve.len()) as i64);
+        for (delta, shift) in [1, 13, 17, 29, 37, 41, 49, 53]
+            .into_iter()
+            .zip([0, 3, 4, 7, 9, 10, 13, 14])
+        {
+            SieveOfAtkin::algorithm_3_1(sieve, lo, hi, delta, shift);
+        }
+        for (delta, shift) in [7, 19, 31, 43].into_iter().zip([1, 5, 8, 11]) {
+            SieveOfAtkin::algorithm_3_2(sieve, lo, hi, delta, shift);
+        }
+        for (delta, shift) in [11, 23, 47, 59].into_iter().zip([2, 6, 12, 15]) {
+            SieveOfAtkin::algorithm_3_3(sieve, lo, hi, delta, shift);
+        }
+    }
+
     /// Of the prime numbers congruent to 1 modulo 4, find those congruent to
     /// `delta` modulo 60.
     ///
+    /// * `sieve` Bitfields to update.
+    /// * `lo` Index of the first bitfield.
+    /// * `hi` Index of the bitfield after the last.
     /// * `delta` Residue.
     /// * `shift` Position of `delta` in a list of coprime residues modulo 60.
-    fn algorithm_3_1(&mut self, delta: i32, shift: u8) {
+    fn algorithm_3_1(sieve: &mut [u16], lo: i64, hi: i64, delta: i32, shift: u8) {
         for f in 1..=15 {
             for g in (1..=30).step_by(2) {
                 let quadratic = 4 * f * f + g * g;
                 if delta == quadratic % 60 {
-                    self.algorithm_4_1(shift, f, g, quadratic / 60);
+                    SieveOfAtkin::algorithm_4_1(sieve, lo, hi, shift, f, g, quadratic / 60);
                 }
             }
         }
@@ -102,14 +321,17 @@ impl SieveOfAtkin {
     /// Of the prime numbers congruent to 1 modulo 6, find those congruent to
     /// `delta` modulo 60.
     ///
+    /// * `sieve` Bitfields to update.
+    /// * `lo` Index of the first bitfield.
+    /// * `hi` Index of the bitfield after the last.
     /// * `delta` Residue.
     /// * `shift` Position of `delta` in a list of coprime residues modulo 60.
-    fn algorithm_3_2(&mut self, delta: i32, shift: u8) {
+    fn algorithm_3_2(sieve: &mut [u16], lo: i64, hi: i64, delta: i32, shift: u8) {
         for f in (1..=10).step_by(2) {
             for g in [2, 4, 8, 10, 14, 16, 20, 22, 26, 28] {
                 let quadratic = 3 * f * f + g * g;
                 if delta == quadratic % 60 {
-                    self.algorithm_4_2(shift, f, g, quadratic / 60);
+                    SieveOfAtkin::algorithm_4_2(sieve, lo, hi, shift, f, g, quadratic / 60);
                 }
             }
         }
@@ -118,15 +340,18 @@ impl SieveOfAtkin {
     /// Of the prime numbers congruent to 11 modulo 12, find those congruent to
     /// `delta` modulo 60.
     ///
+    /// * `sieve` Bitfields to update.
+    /// * `lo` Index of the first bitfield.
+    /// * `hi` Index of the bitfield after the last.
     /// * `delta` Residue.
     /// * `shift` Position of `delta` in a list of coprime residues modulo 60.
-    fn algorithm_3_3(&mut self, delta: i32, shift: u8) {
+    fn algorithm_3_3(sieve: &mut [u16], lo: i64, hi: i64, delta: i32, shift: u8) {
         for (f, gstart) in (1..=10).zip([2, 1].into_iter().cycle()) {
             for g in (gstart..=30).step_by(2) {
                 let quadratic = 3i32 * f * f - g * g;
                 // Remainder can be negative, so perform modulo operation.
                 if delta == quadratic.rem_euclid(60) {
-                    self.algorithm_4_3(shift, f, g, quadratic.div_euclid(60));
+                    SieveOfAtkin::algorithm_4_3(sieve, lo, hi, shift, f, g, quadratic.div_euclid(60));
                 }
             }
         }
@@ -139,13 +364,16 @@ impl SieveOfAtkin {
     /// 4<i>f</i><sup>2</sup> + <i>g</i><sup>2</sup> = 60<i>h</i> + δ
     /// (equivalently: `4f^2 + g^2 = 60h + δ`).
     ///
+    /// * `sieve` Bitfields to update.
+    /// * `lo` Index of the first bitfield.
+    /// * `hi` Index of the bitfield after the last.
     /// * `shift` Position of δ in a list of coprime residues modulo 60.
     /// * `f` Starting abscissa.
     /// * `g` Starting ordinate.
     /// * `h` Multiplier of 60 in the generating curve.
-    fn algorithm_4_1(&mut self, shift: u8, f: i32, g: i32, h: i32) {
+    fn algorithm_4_1(sieve: &mut [u16], lo: i64, hi: i64, shift: u8, f: i32, g: i32, h: i32) {
         let (mut x, mut y0, mut k0) = (f as i64, g as i64, h as i64);
-        while k0 < self.sieve.len() as i64 {
+        while k0 < hi {
             (k0, x) = (k0 + 2 * x + 15, x + 15);
         }
         loop {
@@ -153,12 +381,12 @@ impl SieveOfAtkin {
             if x <= 0 {
                 return;
             }
-            while k0 < 0 {
+            while k0 < lo {
                 (k0, y0) = (k0 + y0 + 15, y0 + 30);
             }
             let (mut k, mut y) = (k0, y0);
-            while k < self.sieve.len() as i64 {
-                self.sieve[k as usize] ^= 1u16 << shift;
+            while k < hi {
+                sieve[(k - lo) as usize] ^= 1u16 << shift;
                 (k, y) = (k + y + 15, y + 30);
             }
         }
@@ -171,13 +399,16 @@ impl SieveOfAtkin {
     /// 3<i>f</i><sup>2</sup> + <i>g</i><sup>2</sup> = 60<i>h</i> + δ
     /// (equivalently: `3f^2 + g^2 = 60h + δ`).
     ///
+    /// * `sieve` Bitfields to update.
+    /// * `lo` Index of the first bitfield.
+    /// * `hi` Index of the bitfield after the last.
     /// * `shift` Position of δ in a list of coprime residues modulo 60.
     /// * `f` Starting abscissa.
     /// * `g` Starting ordinate.
     /// * `h` Multiplier of 60 in the generating curve.
-    fn algorithm_4_2(&mut self, shift: u8, f: i32, g: i32, h: i32) {
+    fn algorithm_4_2(sieve: &mut [u16], lo: i64, hi: i64, shift: u8, f: i32, g: i32, h: i32) {
         let (mut x, mut y0, mut k0) = (f as i64, g as i64, h as i64);
-        while k0 < self.sieve.len() as i64 {
+        while k0 < hi {
             (k0, x) = (k0 + x + 5, x + 10);
         }
         loop {
@@ -185,12 +416,12 @@ impl SieveOfAtkin {
             if x <= 0 {
                 return;
             }
-            while k0 < 0 {
+            while k0 < lo {
                 (k0, y0) = (k0 + y0 + 15, y0 + 30);
             }
             let (mut k, mut y) = (k0, y0);
-            while k < self.sieve.len() as i64 {
-                self.sieve[k as usize] ^= 1u16 << shift;
+            while k < hi {
+                sieve[(k - lo) as usize] ^= 1u16 << shift;
                 (k, y) = (k + y + 15, y + 30);
             }
         }
@@ -203,22 +434,25 @@ impl SieveOfAtkin {
     /// 3<i>f</i><sup>2</sup> − <i>g</i><sup>2</sup> = 60<i>h</i> + δ
     /// (equivalently: `3f^2 - g^2 = 60h + δ`).
     ///
+    /// * `sieve` Bitfields to update.
+    /// * `lo` Index of the first bitfield.
+    /// * `hi` Index of the bitfield after the last.
     /// * `shift` Position of δ in a list of coprime residues modulo 60.
     /// * `f` Starting abscissa.
     /// * `g` Starting ordinate.
     /// * `h` Multiplier of 60 in the generating curve.
-    fn algorithm_4_3(&mut self, shift: u8, f: i32, g: i32, h: i32) {
+    fn algorithm_4_3(sieve: &mut [u16], lo: i64, hi: i64, shift: u8, f: i32, g: i32, h: i32) {
         let (mut x, mut y0, mut k0) = (f as i64, g as i64, h as i64);
         loop {
-            while k0 >= self.sieve.len() as i64 {
+            while k0 >= hi {
                 if x <= y0 {
                     return;
                 }
                 (k0, y0) = (k0 - y0 - 15, y0 + 30);
             }
             let (mut k, mut y) = (k0, y0);
-            while k >= 0 && y < x {
-                self.sieve[k as usize] ^= 1u16 << shift;
+            while k >= lo && y < x {
+                sieve[(k - lo) as usize] ^= 1u16 << shift;
                 (k, y) = (k - y - 15, y + 30);
             }
             (k0, x) = (k0 + x + 5, x + 10);
@@ -227,19 +461,34 @@ impl SieveOfAtkin {
 
     /// Check whether the given number is prime.
     ///
-    /// * `num`
+    /// * `num` Must be in range of the sieve, unless the fallback is enabled.
     pub fn is_prime(&self, num: usize) -> bool {
+        self.checked_is_prime(num)
+            .expect("queried number is out of range of the sieve")
+    }
+
+    /// Check whether the given number is prime.
+    ///
+    /// * `num`
+    ///
+    /// Returns `None` if the number is out of range of the sieve (and the
+    /// fallback is disabled or cannot handle it).
+    pub fn checked_is_prime(&self, num: usize) -> Option<bool> {
         if num < 2 {
-            return false;
+            return Some(false);
         }
         if num == 2 || num == 3 || num == 5 {
-            return true;
+            return Some(true);
         }
         let (num_div_60, num_mod_60) = (num / 60, num % 60);
         if num_div_60 >= self.sieve.len() {
-            panic!("queried number is out of range of the sieve")
+            return if self.fallback {
+                i64::try_from(num).ok().map(utils::is_prime)
+            } else {
+                None
+            };
         }
-        self.sieve[num_div_60] & (1u32 << SieveOfAtkin::SHIFTS[num_mod_60]) as u16 != 0
+        Some(self.sieve[num_div_60] & (1u32 << SieveOfAtkin::SHIFTS[num_mod_60]) as u16 != 0)
     }
 
     /// Yield the found prime numbers in order.
@@ -261,4 +510,362 @@ impl SieveOfAtkin {
             .take_while(|&num| num <= self.limit)
             .map(|num| num as i64)
     }
+
+    /// Yield the found prime numbers in a range in order. The iterator can
+    /// also be consumed from the back.
+    ///
+    /// * `range` Range of numbers. It is truncated to the limit of the sieve.
+    pub fn iter_range(&self, range: impl std::ops::RangeBounds<usize>) -> impl DoubleEndedIterator<Item = i64> + '_ {
+        let lo = match range.start_bound() {
+            std::ops::Bound::Included(&lo) => lo,
+            std::ops::Bound::Excluded(&lo) => lo.saturating_add(1),
+            std::ops::Bound::Unbounded => 0,
+        };
+        // Strict upper bound.
+        let end = match range.end_bound() {
+            std::ops::Bound::Included(&hi) => hi.saturating_add(1),
+            std::ops::Bound::Excluded(&end) => end,
+            std::ops::Bound::Unbounded => usize::MAX,
+        }
+        .min(self.limit + 1);
+        let (lo_idx, hi_idx) = if lo < end {
+            (lo / 60, (end - 1) / 60 + 1)
+        } else {
+            (0, 0)
+        };
+        [2, 3, 5]
+            .into_iter()
+            .chain((lo_idx..hi_idx).flat_map(move |sieve_idx| {
+                let bitfield = self.sieve[sieve_idx];
+                (0..16)
+                    .filter(move |shift| bitfield >> shift & 1 == 1)
+                    .map(move |shift| sieve_idx * 60 + SieveOfAtkin::RESIDUES[shift])
+            }))
+            .filter(move |&num| lo <= num && num < end)
+            .map(|num| num as i64)
+    }
+
+    /// Count the prime numbers less than or equal to the given number.
+    ///
+    /// * `num` Must not exceed the limit of the sieve.
+    pub fn rank(&self, num: usize) -> usize {
+        if num > self.limit {
+            panic!("queried number is out of range of the sieve")
+        }
+        let small = [2, 3, 5].into_iter().filter(|&prime| prime <= num).count();
+        let (num_div_60, num_mod_60) = (num / 60, num % 60);
+        let block_start = num_div_60 - num_div_60 % SieveOfAtkin::RANK_BLOCK_LEN;
+        let preceding = self.sieve[block_start..num_div_60]
+            .iter()
+            .map(|bitfield| bitfield.count_ones() as usize)
+            .sum::<usize>();
+        let mask = (1u32 << SieveOfAtkin::RESIDUES.partition_point(|&residue| residue <= num_mod_60)) - 1;
+        let current = (self.sieve[num_div_60] as u32 & mask).count_ones() as usize;
+        small + self.ranks[num_div_60 / SieveOfAtkin::RANK_BLOCK_LEN] + preceding + current
+    }
+
+    /// Find a prime number by its position in the sequence of prime numbers.
+    ///
+    /// * `idx` Zero-based index. (The prime number at index 0 is 2.)
+    ///
+    /// Returns the prime number, if it does not exceed the limit of the
+    /// sieve.
+    pub fn nth_prime(&self, idx: usize) -> Option<i64> {
+        if idx < 3 {
+            return [2, 3, 5]
+                .into_iter()
+                .nth(idx)
+                .filter(|&prime| prime <= self.limit as i64);
+        }
+        let mut remaining = idx - 3;
+        let block_idx = self.ranks.partition_point(|&rank| rank <= remaining).checked_sub(1)?;
+        remaining -= self.ranks[block_idx];
+        let block_start = block_idx * SieveOfAtkin::RANK_BLOCK_LEN;
+        let block_end = self.sieve.len().min(block_start + SieveOfAtkin::RANK_BLOCK_LEN);
+        for sieve_idx in block_start..block_end {
+            let mut bitfield = self.sieve[sieve_idx];
+            let count = bitfield.count_ones() as usize;
+            if remaining >= count {
+                remaining -= count;
+                continue;
+            }
+            // Clear the lowest set bits until the required one is the lowest.
+            for _ in 0..remaining {
+                bitfield &= bitfield - 1;
+            }
+            let num = sieve_idx * 60 + SieveOfAtkin::RESIDUES[bitfield.trailing_zeros() as usize];
+            return if num <= self.limit { Some(num as i64) } else { None };
+        }
+        None
+    }
+
+    /// Find the smallest prime number greater than the given number.
+    ///
+    /// * `num`
+    ///
+    /// Returns the prime number, if it does not exceed the limit of the
+    /// sieve.
+    pub fn next_prime(&self, num: usize) -> Option<i64> {
+        if num >= self.limit {
+            return None;
+        }
+        self.nth_prime(self.rank(num))
+    }
+
+    /// Find the largest prime number less than the given number.
+    ///
+    /// * `num`
+    ///
+    /// Returns the prime number, if the given number does not exceed the
+    /// limit of the sieve by more than 1.
+    pub fn prev_prime(&self, num: usize) -> Option<i64> {
+        if num <= 2 || num - 1 > self.limit {
+            return None;
+        }
+        self.nth_prime(self.rank(num - 1) - 1)
+    }
+}
+
+/// Read-only view of a sieve of Atkin serialised by `SieveOfAtkin::to_bytes`.
+/// The bitfields are read directly from the borrowed bytes, so that a file
+/// read once can be queried without copying or re-sieving.
+pub struct SieveView<'a> {
+    limit: usize,
+    bitfields: &'a [u8],
+}
+
+impl<'a> SieveView<'a> {
+    /// Construct a view of a serialised sieve of Atkin.
+    ///
+    /// * `bytes`
+    ///
+    /// Returns an error if the bytes are not a valid serialisation.
+    pub fn new(bytes: &'a [u8]) -> Result<SieveView<'a>, SieveError> {
+        if bytes.len() < SieveOfAtkin::HEADER_LEN {
+            return Err(SieveError::LengthMismatch);
+        }
+        let (header, bitfields) = bytes.split_at(SieveOfAtkin::HEADER_LEN);
+        if header[0..4] != SieveOfAtkin::MAGIC {
+            return Err(SieveError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        if version != SieveOfAtkin::VERSION {
+            return Err(SieveError::UnsupportedVersion(version));
+        }
+        let limit = u64::from_le_bytes(header[8..16].try_into().unwrap());
+        let limit = usize::try_from(limit).map_err(|_| SieveError::Overflow)?;
+        SieveOfAtkin::round(limit)?;
+        if bitfields.len() / 2 != limit / 60 + 1 || bitfields.len() % 2 != 0 {
+            return Err(SieveError::LengthMismatch);
+        }
+        if u64::from_le_bytes(header[16..24].try_into().unwrap()) != SieveOfAtkin::checksum(bytes) {
+            return Err(SieveError::ChecksumMismatch);
+        }
+        Ok(SieveView { limit, bitfields })
+    }
+
+    /// Obtain the number the sieve was constructed up to.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Obtain a bitfield.
+    ///
+    /// * `sieve_idx`
+    fn bitfield(&self, sieve_idx: usize) -> u16 {
+        u16::from_le_bytes([self.bitfields[2 * sieve_idx], self.bitfields[2 * sieve_idx + 1]])
+    }
+
+    /// Check whether the given number is prime.
+    ///
+    /// * `num` Must be in range of the sieve.
+    pub fn is_prime(&self, num: usize) -> bool {
+        self.checked_is_prime(num)
+            .expect("queried number is out of range of the sieve")
+    }
+
+    /// Check whether the given number is prime.
+    ///
+    /// * `num`
+    ///
+    /// Returns `None` if the number is out of range of the sieve.
+    pub fn checked_is_prime(&self, num: usize) -> Option<bool> {
+        if num < 2 {
+            return Some(false);
+        }
+        if num == 2 || num == 3 || num == 5 {
+            return Some(true);
+        }
+        let (num_div_60, num_mod_60) = (num / 60, num % 60);
+        if 2 * num_div_60 >= self.bitfields.len() {
+            return None;
+        }
+        Some(self.bitfield(num_div_60) & (1u32 << SieveOfAtkin::SHIFTS[num_mod_60]) as u16 != 0)
+    }
+
+    /// Yield the found prime numbers in order.
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        [2, 3, 5]
+            .into_iter()
+            .chain((0..self.bitfields.len() / 2).flat_map(move |sieve_idx| {
+                let bitfield = self.bitfield(sieve_idx);
+                (0..16)
+                    .filter(move |shift| bitfield >> shift & 1 == 1)
+                    .map(move |shift| sieve_idx * 60 + SieveOfAtkin::RESIDUES[shift])
+            }))
+            .take_while(|&num| num <= self.limit)
+            .map(|num| num as i64)
+    }
+}
+
+/// Error returned when a sieve cannot be constructed, saved or loaded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SieveError {
+    /// The limit is too large for the bounds of the sieve to be represented.
+    Overflow,
+    /// The memory for the sieve cannot be allocated.
+    Allocation,
+    /// The file cannot be read or written.
+    Io(std::io::ErrorKind),
+    /// The data does not start with the expected magic bytes.
+    InvalidMagic,
+    /// The data is in a format version which is not supported.
+    UnsupportedVersion(u32),
+    /// The length of the data does not match the limit in its header.
+    LengthMismatch,
+    /// The checksum of the data does not match the one in its header.
+    ChecksumMismatch,
+}
+
+impl std::fmt::Display for SieveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SieveError::Overflow => write!(f, "overflow detected; argument too large"),
+            SieveError::Allocation => write!(f, "memory allocation failed"),
+            SieveError::Io(kind) => write!(f, "input/output error: {}", kind),
+            SieveError::InvalidMagic => write!(f, "data is not a serialised sieve"),
+            SieveError::UnsupportedVersion(version) => write!(f, "unsupported format version {}", version),
+            SieveError::LengthMismatch => write!(f, "length of data does not match limit"),
+            SieveError::ChecksumMismatch => write!(f, "checksum of data does not match"),
+        }
+    }
+}
+
+impl std::error::Error for SieveError {}
+
+/// Segmented sieve which finds the prime numbers in a window far from 0
+/// without sieving everything below it. The base primes up to the square
+/// root of the upper bound are generated using the sieve of Atkin; their
+/// multiples are then crossed out of the window (using the same bitfield
+/// layout as the sieve of Atkin) one cache-sized block at a time.
+pub struct SegmentedSieve {
+    lo: usize,
+    hi: usize,
+    // Index of the first bitfield of the window, were it part of a sieve
+    // starting from 0.
+    offset: usize,
+    sieve: Vec<u16>,
+}
+
+impl SegmentedSieve {
+    // Number of bitfields crossed out at a time. Their total size (32 KiB)
+    // fits in the L1 cache of most processors.
+    const BLOCK_LEN: usize = 1 << 14;
+
+    /// Construct a segmented sieve over the given window.
+    ///
+    /// * `lo` Non-strict lower bound.
+    /// * `hi` Non-strict upper bound.
+    pub fn new(lo: usize, hi: usize) -> SegmentedSieve {
+        if lo > hi {
+            panic!("lower bound of the window exceeds upper bound");
+        }
+        if hi.checked_add(60).is_none() {
+            panic!("overflow detected; argument too large");
+        }
+        let offset = lo / 60;
+        let mut segmented_sieve = SegmentedSieve {
+            lo,
+            hi,
+            offset,
+            sieve: vec![u16::MAX; hi / 60 - offset + 1],
+        };
+        segmented_sieve.init();
+        segmented_sieve
+    }
+
+    /// Initialise the segmented sieve.
+    fn init(&mut self) {
+        // 1 is the only number in the first bitfield which has no prime
+        // factor, so it will not get crossed out.
+        if self.offset == 0 {
+            self.sieve[0] &= !1;
+        }
+        let base_primes = SieveOfAtkin::new(utils::isqrt(self.hi as i64) as usize)
+            .iter()
+            .skip(3)
+            .map(|prime| prime as usize)
+            .collect::<Vec<usize>>();
+        for block_start in (0..self.sieve.len()).step_by(SegmentedSieve::BLOCK_LEN) {
+            let block_end = self.sieve.len().min(block_start + SegmentedSieve::BLOCK_LEN);
+            let (block_lo, block_hi) = ((self.offset + block_start) * 60, (self.offset + block_end) * 60);
+            for &prime in base_primes.iter().take_while(|&&prime| prime * prime < block_hi) {
+                // Only multiples which are coprime to 60 are represented in
+                // the sieve. These are the products of the prime and numbers
+                // coprime to 60, so step through the latter using the
+                // differences between consecutive coprime residues.
+                let mut factor = prime.max(block_lo.div_ceil(prime));
+                while SieveOfAtkin::SHIFTS[factor % 60] == 16 {
+                    factor += 1;
+                }
+                let mut offset = SieveOfAtkin::OFFSETS
+                    .iter()
+                    .cycle()
+                    .skip(SieveOfAtkin::SHIFTS[factor % 60] as usize);
+                let mut multiple = prime * factor;
+                while multiple < block_hi {
+                    self.sieve[multiple / 60 - self.offset] &= !(1u32 << SieveOfAtkin::SHIFTS[multiple % 60]) as u16;
+                    multiple += prime * offset.next().unwrap();
+                }
+            }
+        }
+    }
+
+    /// Check whether the given number is prime.
+    ///
+    /// * `num` Must lie in the window.
+    pub fn is_prime(&self, num: usize) -> bool {
+        if num < self.lo || num > self.hi {
+            panic!("queried number is out of range of the sieve")
+        }
+        if num < 2 {
+            return false;
+        }
+        if num == 2 || num == 3 || num == 5 {
+            return true;
+        }
+        self.sieve[num / 60 - self.offset] & (1u32 << SieveOfAtkin::SHIFTS[num % 60]) as u16 != 0
+    }
+
+    /// Yield the prime numbers in the window in order.
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        let mut num: usize = self.offset * 60 + 1;
+        let mut offset = SieveOfAtkin::OFFSETS.iter().cycle();
+        [2, 3, 5]
+            .into_iter()
+            .filter(|&prime| self.lo <= prime)
+            .chain(
+                self.sieve
+                    .iter()
+                    .flat_map(|bitfield| (0..16).map(move |shift| bitfield >> shift & 1 == 1))
+                    .filter_map(move |is_prime| {
+                        let filtered = if is_prime { Some(num) } else { None };
+                        num += offset.next().unwrap();
+                        filtered
+                    }),
+            )
+            .skip_while(|&num| num < self.lo)
+            .take_while(|&num| num <= self.hi)
+            .map(|num| num as i64)
+    }
 }
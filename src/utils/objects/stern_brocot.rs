@@ -0,0 +1,211 @@
+use crate::utils;
+
+/// Walker over the Stern–Brocot tree, which contains every positive rational
+/// number exactly once, in lowest form. Each node is the mediant of the
+/// nearest ancestors to its left and right. Moves are made in batches, so
+/// that deep nodes can be reached quickly.
+#[derive(Clone)]
+pub struct SternBrocot {
+    // Numerators and denominators of the nearest ancestors to the left and
+    // right of the current node. Initially, these are 0/1 and 1/0.
+    left: (utils::Long, utils::Long),
+    right: (utils::Long, utils::Long),
+    // Directions and lengths of the moves made from the root.
+    path: Vec<(char, u64)>,
+}
+
+impl SternBrocot {
+    /// Construct a walker positioned at the root of the Stern–Brocot tree,
+    /// which is 1/1.
+    pub fn new() -> SternBrocot {
+        SternBrocot {
+            left: (utils::Long::zero(), utils::Long::one()),
+            right: (utils::Long::one(), utils::Long::zero()),
+            path: vec![],
+        }
+    }
+
+    /// Construct a walker positioned at the node reached by following a path
+    /// from the root.
+    ///
+    /// * `path` String of the characters `L` and `R`, indicating moves to the
+    ///   left and right child respectively.
+    ///
+    /// Returns `None` if the path contains any other character.
+    pub fn from_path(path: &str) -> Option<SternBrocot> {
+        let mut stern_brocot = SternBrocot::new();
+        for direction in path.chars() {
+            match direction {
+                'L' => stern_brocot.go_left(1),
+                'R' => stern_brocot.go_right(1),
+                _ => return None,
+            }
+        }
+        Some(stern_brocot)
+    }
+
+    /// Construct a walker positioned at the node equal to a rational number.
+    ///
+    /// * `fraction` Must be positive.
+    pub fn find(fraction: &utils::Fraction) -> SternBrocot {
+        if fraction.numerator().signum() <= 0 {
+            panic!("only positive rational numbers are in the Stern–Brocot tree");
+        }
+        let mut stern_brocot = SternBrocot::new();
+        stern_brocot.descend(fraction.numerator().magnitude(), fraction.denominator(), None);
+        stern_brocot
+    }
+
+    /// Find the neighbours of a rational number among the rational numbers
+    /// whose denominators do not exceed a limit. (In the interval from 0 to
+    /// 1, these are the neighbours in the Farey sequence of that order.)
+    ///
+    /// * `fraction` Must be positive.
+    /// * `max_denominator` Must be positive.
+    ///
+    /// Returns the largest such rational number less than the given one and
+    /// the smallest such rational number greater than it.
+    pub fn neighbours(
+        fraction: &utils::Fraction,
+        max_denominator: &utils::Long,
+    ) -> (utils::Fraction, utils::Fraction) {
+        if fraction.numerator().signum() <= 0 {
+            panic!("only positive rational numbers are in the Stern–Brocot tree");
+        }
+        if max_denominator.is_zero() {
+            panic!("limit on the denominator must be positive");
+        }
+        let mut stern_brocot = SternBrocot::new();
+        let (left, right) = if stern_brocot.descend(
+            fraction.numerator().magnitude(),
+            fraction.denominator(),
+            Some(max_denominator),
+        ) {
+            // The rational number is itself admissible. Its neighbours lie in
+            // its left and right subtrees, in the directions of its nearest
+            // ancestors.
+            let node = stern_brocot.mediant();
+            let mul_add = |ancestor: &(utils::Long, utils::Long)| {
+                let count = &(max_denominator - &ancestor.1) / &node.1;
+                (&ancestor.0 + &(&node.0 * &count), &ancestor.1 + &(&node.1 * &count))
+            };
+            (mul_add(&stern_brocot.left), mul_add(&stern_brocot.right))
+        } else {
+            (stern_brocot.left, stern_brocot.right)
+        };
+        let to_fraction = |(numerator, denominator)| {
+            utils::Fraction::new(utils::BigInt::from(numerator), utils::BigInt::from(denominator))
+        };
+        (to_fraction(left), to_fraction(right))
+    }
+
+    /// Obtain the rational number at the current node.
+    pub fn node(&self) -> utils::Fraction {
+        let (numerator, denominator) = self.mediant();
+        utils::Fraction::new(utils::BigInt::from(numerator), utils::BigInt::from(denominator))
+    }
+
+    /// Obtain the path from the root to the current node as a string of the
+    /// characters `L` and `R`.
+    pub fn path(&self) -> String {
+        self.path
+            .iter()
+            .map(|&(direction, count)| direction.to_string().repeat(count as usize))
+            .collect()
+    }
+
+    /// Move to the left child of the current node repeatedly.
+    ///
+    /// * `count` Number of moves.
+    pub fn go_left(&mut self, count: u64) {
+        let count_long = utils::Long::from(count);
+        self.right = (
+            &self.right.0 + &(&self.left.0 * &count_long),
+            &self.right.1 + &(&self.left.1 * &count_long),
+        );
+        self.record('L', count);
+    }
+
+    /// Move to the right child of the current node repeatedly.
+    ///
+    /// * `count` Number of moves.
+    pub fn go_right(&mut self, count: u64) {
+        let count_long = utils::Long::from(count);
+        self.left = (
+            &self.left.0 + &(&self.right.0 * &count_long),
+            &self.left.1 + &(&self.right.1 * &count_long),
+        );
+        self.record('R', count);
+    }
+
+    /// Append moves to the path.
+    ///
+    /// * `direction`
+    /// * `count`
+    fn record(&mut self, direction: char, count: u64) {
+        if count == 0 {
+            return;
+        }
+        match self.path.last_mut() {
+            Some((last, last_count)) if *last == direction => *last_count += count,
+            _ => self.path.push((direction, count)),
+        }
+    }
+
+    /// Calculate the numerator and denominator of the current node.
+    fn mediant(&self) -> (utils::Long, utils::Long) {
+        (&self.left.0 + &self.right.0, &self.left.1 + &self.right.1)
+    }
+
+    /// Move towards a rational number, stopping if it is reached or if the
+    /// denominator of the current node would exceed a limit.
+    ///
+    /// * `numerator` Must be positive.
+    /// * `denominator` Must be positive.
+    /// * `max_denominator`
+    ///
+    /// Returns whether the rational number was reached.
+    fn descend(
+        &mut self,
+        numerator: &utils::Long,
+        denominator: &utils::Long,
+        max_denominator: Option<&utils::Long>,
+    ) -> bool {
+        loop {
+            let node = self.mediant();
+            if max_denominator.is_some_and(|max_denominator| node.1 > *max_denominator) {
+                return false;
+            }
+
+            // Each batch of moves in one direction continues for as long as
+            // the rational number stays on the same side of the current node.
+            // The distances from the nearest ancestors determine the length of
+            // the batch.
+            let left_distance = &(numerator * &self.left.1) - &(denominator * &self.left.0);
+            let right_distance = &(denominator * &self.right.0) - &(numerator * &self.right.1);
+            match (numerator * &node.1).cmp(&(denominator * &node.0)) {
+                std::cmp::Ordering::Equal => return true,
+                std::cmp::Ordering::Less => {
+                    let mut count = &(&right_distance - 1) / &left_distance;
+                    if let Some(max_denominator) = max_denominator {
+                        count = count.min(&(max_denominator - &self.right.1) / &self.left.1);
+                    }
+                    self.go_left(u64::try_from(&count).unwrap());
+                }
+                std::cmp::Ordering::Greater => {
+                    let mut count = &(&left_distance - 1) / &right_distance;
+                    if let Some(max_denominator) = max_denominator.filter(|_| !self.right.1.is_zero()) {
+                        count = count.min(&(max_denominator - &self.left.1) / &self.right.1);
+                    }
+                    self.go_right(u64::try_from(&count).unwrap());
+                }
+            }
+        }
+    }
+}
+
+impl Default for SternBrocot {
+    fn default() -> SternBrocot {
+        SternBrocot::new()
+    }
+}